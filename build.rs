@@ -1,7 +1,16 @@
-use std::{env, error::Error};
+use std::error::Error;
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let raylib_out_dir: String = env::var("OUT_DIR").unwrap();
+    // without the raylib feature the maze library is built on its own
+    #[cfg(feature = "raylib")]
+    link_raylib();
+
+    Ok(())
+}
+
+#[cfg(feature = "raylib")]
+fn link_raylib() {
+    let raylib_out_dir: String = std::env::var("OUT_DIR").unwrap();
     let dst = cmake::build("raylib");
     println!("raylib: {}", raylib_out_dir);
     cc::Build::new()
@@ -19,6 +28,4 @@ fn main() -> Result<(), Box<dyn Error>> {
     // Link to the library
     println!("cargo:rustc-link-search=native={raylib_out_dir}/lib");
     println!("cargo:rustc-link-lib=static=raylib");
-
-    Ok(())
 }
@@ -0,0 +1,289 @@
+use std::{fmt::Write as _, fs};
+
+use serde::Serialize;
+
+use generative::maze::{
+    analysis, generator,
+    headless::{self, GenerationStats},
+    solver,
+};
+
+const USAGE: &str = "usage: bench [--seeds N] [--sizes WxH,WxH] [--generators NAME,NAME] \
+[--solvers NAME,NAME] [--csv FILE] [--json FILE]
+
+Runs every generator with every solver and prints the results as csv.";
+
+/// One generator and solver run on one board.
+#[derive(Clone, Debug, Serialize)]
+struct Record {
+    generator: String,
+    solver: String,
+    seed: u64,
+    width: usize,
+    height: usize,
+    generation_steps: usize,
+    generation_ms: f64,
+    solver_steps: usize,
+    solver_ms: f64,
+    solved: bool,
+    error: Option<String>,
+    /// steps from the start to the goal, cells on the path minus one like
+    /// in `analysis::Statistics`, `None` when the solver gave up
+    solution_length: Option<usize>,
+    expanded: Option<usize>,
+    dead_ends: usize,
+    straight_share: f64,
+    turn_share: f64,
+    river: f64,
+    diameter: usize,
+    solution_ratio: f64,
+}
+
+struct Options {
+    seeds: u64,
+    sizes: Vec<(usize, usize)>,
+    generators: Vec<String>,
+    solvers: Vec<String>,
+    csv: Option<String>,
+    json: Option<String>,
+}
+
+fn parse_size(size: &str) -> Result<(usize, usize), String> {
+    let (width, height) = size
+        .split_once('x')
+        .ok_or_else(|| format!("invalid size '{}', expected WxH", size))?;
+    let width: usize = width
+        .parse()
+        .map_err(|_| format!("invalid width in '{}'", size))?;
+    let height: usize = height
+        .parse()
+        .map_err(|_| format!("invalid height in '{}'", size))?;
+    if width == 0 || height == 0 {
+        return Err(format!("invalid size '{}'", size));
+    }
+    Ok((width, height))
+}
+
+fn names(list: &str, known: &[&str]) -> Result<Vec<String>, String> {
+    list.split(',')
+        .map(|name| {
+            if known.contains(&name) {
+                Ok(name.to_string())
+            } else {
+                Err(format!(
+                    "unknown algorithm '{}', expected one of: {}",
+                    name,
+                    known.join(", ")
+                ))
+            }
+        })
+        .collect()
+}
+
+fn parse_args() -> Result<Options, String> {
+    let mut options = Options {
+        seeds: 3,
+        sizes: vec![(10, 10), (50, 50), (100, 100)],
+        generators: generator::ALGORITHMS
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+        solvers: solver::SOLVERS
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+        csv: None,
+        json: None,
+    };
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "-h" || arg == "--help" {
+            println!("{}", USAGE);
+            std::process::exit(0);
+        }
+        let value = args
+            .next()
+            .ok_or_else(|| format!("missing value for {}\n\n{}", arg, USAGE))?;
+        match arg.as_str() {
+            "--seeds" => {
+                options.seeds = value
+                    .parse()
+                    .map_err(|_| format!("invalid number of seeds '{}'", value))?
+            }
+            "--sizes" => {
+                options.sizes = value.split(',').map(parse_size).collect::<Result<_, _>>()?
+            }
+            "--generators" => options.generators = names(&value, &generator::ALGORITHMS)?,
+            "--solvers" => options.solvers = names(&value, &solver::SOLVERS)?,
+            "--csv" => options.csv = Some(value),
+            "--json" => options.json = Some(value),
+            _ => return Err(format!("unknown option {}\n\n{}", arg, USAGE)),
+        }
+    }
+    Ok(options)
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn to_csv(records: &[Record]) -> String {
+    let mut csv = String::from(
+        "generator,solver,seed,width,height,generation_steps,generation_ms,solver_steps,\
+solver_ms,solved,error,solution_length,expanded,dead_ends,straight_share,turn_share,river,\
+diameter,solution_ratio\n",
+    );
+    for record in records {
+        let _ = writeln!(
+            csv,
+            "{},{},{},{},{},{},{:.3},{},{:.3},{},{},{},{},{},{:.4},{:.4},{:.4},{},{:.4}",
+            csv_field(&record.generator),
+            csv_field(&record.solver),
+            record.seed,
+            record.width,
+            record.height,
+            record.generation_steps,
+            record.generation_ms,
+            record.solver_steps,
+            record.solver_ms,
+            record.solved,
+            csv_field(record.error.as_deref().unwrap_or_default()),
+            record
+                .solution_length
+                .map(|length| length.to_string())
+                .unwrap_or_default(),
+            record
+                .expanded
+                .map(|expanded| expanded.to_string())
+                .unwrap_or_default(),
+            record.dead_ends,
+            record.straight_share,
+            record.turn_share,
+            record.river,
+            record.diameter,
+            record.solution_ratio,
+        );
+    }
+    csv
+}
+
+fn bench(options: &Options) -> Vec<Record> {
+    let mut records = Vec::new();
+    for &(width, height) in &options.sizes {
+        for generator_name in &options.generators {
+            for seed in 0..options.seeds {
+                let (board, GenerationStats { steps, elapsed }) =
+                    headless::generate(width, height, |board| {
+                        generator::by_name(generator_name, board, seed).unwrap()
+                    });
+                let stats = analysis::statistics(&board);
+
+                for solver_name in &options.solvers {
+                    let mut solver = solver::by_name(solver_name, &board, seed).unwrap();
+                    let limit = 100 * board.cells.len() + 1000;
+                    let result = headless::solve(&board, solver.as_mut(), limit);
+
+                    records.push(Record {
+                        generator: generator_name.clone(),
+                        solver: solver_name.clone(),
+                        seed,
+                        width,
+                        height,
+                        generation_steps: steps,
+                        generation_ms: elapsed.as_secs_f64() * 1000.0,
+                        solver_steps: result.steps,
+                        solver_ms: result.elapsed.as_secs_f64() * 1000.0,
+                        solved: result.solved,
+                        error: result.error,
+                        solution_length: result
+                            .solved
+                            .then(|| solver.get_path().len().saturating_sub(1)),
+                        expanded: solver.expanded(),
+                        dead_ends: stats.dead_ends,
+                        straight_share: stats.straight_share,
+                        turn_share: stats.turn_share,
+                        river: stats.river,
+                        diameter: stats.diameter,
+                        solution_ratio: stats.solution_ratio,
+                    });
+                }
+                eprintln!("{}x{} {} seed {}", width, height, generator_name, seed);
+            }
+        }
+    }
+    records
+}
+
+fn main() {
+    let options = match parse_args() {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(2);
+        }
+    };
+
+    let records = bench(&options);
+    let csv = to_csv(&records);
+    if let Some(file) = &options.csv
+        && let Err(err) = fs::write(file, &csv)
+    {
+        eprintln!("{}: {}", file, err);
+        std::process::exit(1);
+    }
+    if let Some(file) = &options.json {
+        let json = serde_json::to_string_pretty(&records).expect("records are valid json");
+        if let Err(err) = fs::write(file, json) {
+            eprintln!("{}: {}", file, err);
+            std::process::exit(1);
+        }
+    }
+    if options.csv.is_none() && options.json.is_none() {
+        print!("{}", csv);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unsolved_runs_have_no_solution_length() {
+        let options = Options {
+            seeds: 1,
+            sizes: vec![(6, 5)],
+            generators: vec![String::from("prim")],
+            solvers: vec![String::from("breadth first search")],
+            csv: None,
+            json: None,
+        };
+        let mut records = bench(&options);
+        let (board, _) =
+            headless::generate(6, 5, |board| generator::by_name("prim", board, 0).unwrap());
+        assert_eq!(
+            records[0].solution_length,
+            analysis::statistics(&board).solution_length
+        );
+        let mut unsolved = records[0].clone();
+        unsolved.solved = false;
+        unsolved.solution_length = None;
+        records.push(unsolved);
+
+        let csv = to_csv(&records);
+        let rows: Vec<Vec<&str>> = csv.lines().map(|line| line.split(',').collect()).collect();
+        let column = rows[0]
+            .iter()
+            .position(|&name| name == "solution_length")
+            .unwrap();
+        assert!(rows[1][column].parse::<usize>().is_ok());
+        assert_eq!(rows[2][column], "");
+
+        let json = serde_json::to_value(&records).unwrap();
+        assert!(json[0]["solution_length"].is_u64());
+        assert!(json[1]["solution_length"].is_null());
+    }
+}
@@ -1,59 +1,311 @@
 use generative::{
     maze::{
-        Board, Generator, Solver, State,
-        generator::{
-            aldous_broder::AldousBroder, backtracking::Backtracking, binary_tree::BinaryTree,
-            eller::Eller, growing_tree::GrowingTree, hunt_and_kill::HuntAndKill, kruskal::Kruskal,
-            prim::Prim, recursive_division::RecursiveDivision, sidewinder::Sidewinder,
-            wilson::Wilson,
-        },
-        path, solver,
+        Board, Generator, Solver, State, Wrap, braid,
+        delta::DeltaBoard,
+        generator::{self, backtracking::Backtracking},
+        layered::LayeredBoard,
+        mask::Mask,
+        polar::PolarBoard,
+        raster::{self, RasterOptions},
+        render::{RaylibRenderer, Renderer, Scaled, Style},
+        solver::{self, djikstra::Djikstra},
+        storage::{Metadata, SavedMaze},
+        svg::{self, SvgOptions},
+        topology::Topology,
     },
-    raygui, raylib, str,
+    raygui,
+    raylib::{self, MouseButton},
+    str,
 };
 
-use std::ffi::CString;
+use std::{ffi::CString, io};
 
 const SCREEN_WIDTH: usize = 1200;
 const SCREEN_HEIGHT: usize = 800;
 const TITLE: &str = "";
 const BORDER: usize = 5;
+const PANEL_WIDTH: usize = 350;
+const SAVE_FILE: &str = "maze.json";
+/// The wrap options in the order of the combo box.
+const WRAPS: [Wrap; 3] = [Wrap::None, Wrap::Cylinder, Wrap::Torus];
+/// Index of the layered board in the combo box, after the wrap options.
+const LAYERED_MODE: i32 = WRAPS.len() as i32;
+/// Index of the polar board in the combo box.
+const POLAR_MODE: i32 = LAYERED_MODE + 1;
+/// Index of the triangle board in the combo box.
+const DELTA_MODE: i32 = POLAR_MODE + 1;
+/// Number of levels of the layered board.
+const LEVELS: usize = 3;
+
+/// A maze on another grid than the flat board, with its own generator and
+/// solver, shown instead of the flat board.
+struct Grid<T> {
+    board: T,
+    generator: Box<dyn Generator<T>>,
+    solver: Box<dyn Solver<T>>,
+}
+
+/// What the main loop does with a `Grid`, whatever kind of grid it is.
+trait Shown {
+    fn reset_solver(&mut self);
+    fn solution(&self) -> (&[usize], Option<usize>);
+    fn frame(
+        &mut self,
+        step_by_step: bool,
+        step: &mut bool,
+        error: &mut Option<String>,
+        renderer: &mut dyn Renderer,
+    );
+    fn write_svg(&self, done: bool) -> io::Result<()>;
+    fn write_png(&self, solving: bool) -> io::Result<()>;
+}
+
+impl<T: Topology + 'static> Shown for Grid<T> {
+    fn reset_solver(&mut self) {
+        self.solver = init_grid_solver(&self.board);
+    }
+
+    fn solution(&self) -> (&[usize], Option<usize>) {
+        (self.solver.get_path(), self.solver.expanded())
+    }
+
+    fn frame(
+        &mut self,
+        step_by_step: bool,
+        step: &mut bool,
+        error: &mut Option<String>,
+        renderer: &mut dyn Renderer,
+    ) {
+        self.board.draw_board(&Style::default(), renderer);
+        update(
+            &mut self.board,
+            self.generator.as_mut(),
+            &mut self.solver,
+            init_grid_solver,
+            |_| {},
+            step_by_step,
+            step,
+            error,
+            renderer,
+        );
+    }
+
+    fn write_svg(&self, done: bool) -> io::Result<()> {
+        let solution = done.then(|| self.solver.get_path().as_slice());
+        svg::write_svg("maze.svg", &self.board, solution, &SvgOptions::default())
+    }
+
+    fn write_png(&self, solving: bool) -> io::Result<()> {
+        let solver = solving.then(|| self.solver.as_ref());
+        raster::write_png("maze.png", &self.board, solver, &RasterOptions::default())
+    }
+}
 
 static mut STATE: State = State::Wait;
 static mut SELECTED_GENERATOR: i32 = 0;
 static mut SELECTED_SOLVER: i32 = 0;
 static mut STEP: usize = 0;
 static mut FAST: bool = false;
+static mut SEED: u64 = 0;
+static mut BRAID: f32 = 0.0;
+static mut SELECTED_WRAP: i32 = 0;
+/// The shape given on the command line, used for every maze of its size.
+static mut MASK: Option<Mask> = None;
+/// The maze on the other grid, while the layered, the polar or the
+/// triangle board is selected.
+static mut GRID: Option<Box<dyn Shown>> = None;
 
 fn init_solver(board: &Board) -> Box<dyn Solver> {
+    unsafe { solver::by_name(solver::SOLVERS[SELECTED_SOLVER as usize], board, SEED).unwrap() }
+}
+
+/// Only some solvers work on the other grids, the others fall back to
+/// djikstra.
+fn init_grid_solver<T: Topology + 'static>(board: &T) -> Box<dyn Solver<T>> {
+    unsafe {
+        solver::for_topology(solver::SOLVERS[SELECTED_SOLVER as usize], board).unwrap_or_else(
+            || {
+                SELECTED_SOLVER = 0;
+                Box::new(Djikstra::new(board))
+            },
+        )
+    }
+}
+
+/// Number of cells shown across and down for a board of `width` x
+/// `height`.
+fn shown_size(width: usize, height: usize) -> (usize, usize) {
+    unsafe {
+        match SELECTED_WRAP {
+            // the levels of a layered board are side by side, one cell apart
+            LAYERED_MODE => (LEVELS * (width + 1) - 1, height),
+            // a polar board has one ring for every row
+            POLAR_MODE => (2 * height, 2 * height),
+            // triangles overlap by half their base
+            DELTA_MODE => ((width + 1).div_ceil(2), height),
+            _ => (width, height),
+        }
+    }
+}
+
+fn fit_cell_size(width: usize, height: usize) -> usize {
+    let (width, height) = shown_size(width, height);
+    ((SCREEN_WIDTH - PANEL_WIDTH - 2 * BORDER) / width)
+        .min((SCREEN_HEIGHT - 2 * BORDER) / height)
+        .max(1)
+}
+
+/// Boards with more cells than pixels are drawn scaled down to fit the
+/// window, all others at full size.
+fn fit_scale(width: usize, height: usize) -> f32 {
+    let (width, height) = shown_size(width, height);
+    ((SCREEN_WIDTH - PANEL_WIDTH - 2 * BORDER) as f32 / width as f32)
+        .min((SCREEN_HEIGHT - 2 * BORDER) as f32 / height as f32)
+        .min(1.0)
+}
+
+/// Only some generators work on the other grids, the others fall back to
+/// the recursive backtracker.
+fn init_grid<T: Topology + 'static>(mut board: T, seed: u64) -> Box<dyn Shown> {
     unsafe {
-        match SELECTED_SOLVER {
-            0 => Box::new(solver::djikstra::Djikstra::new(board)),
-            1 => Box::new(solver::backtracker::Backtracker::new(board)),
-            2 => Box::new(solver::a_star::AStar::new(board)),
-            3 => Box::new(solver::dead_end_filing::DeadEndFilling::new(board)),
-            _ => panic!(),
+        let generator = generator::for_topology(
+            generator::ALGORITHMS[SELECTED_GENERATOR as usize],
+            &mut board,
+            seed,
+        )
+        .unwrap_or_else(|| {
+            SELECTED_GENERATOR = 0;
+            Box::new(Backtracking::with_seed(seed))
+        });
+        let solver = init_grid_solver(&board);
+        Box::new(Grid {
+            board,
+            generator,
+            solver,
+        })
+    }
+}
+
+/**
+ * Advance the generator or the solver by one frame and draw their state,
+ * for the flat and the layered board alike. `generated` runs once the
+ * maze is finished.
+ */
+#[allow(clippy::too_many_arguments)]
+fn update<T: Topology + ?Sized>(
+    board: &mut T,
+    generator: &mut dyn Generator<T>,
+    solver: &mut Box<dyn Solver<T>>,
+    init_solver: fn(&T) -> Box<dyn Solver<T>>,
+    generated: fn(&mut T),
+    step_by_step: bool,
+    step: &mut bool,
+    error: &mut Option<String>,
+    renderer: &mut dyn Renderer,
+) {
+    unsafe {
+        match STATE {
+            State::Wait => {}
+            State::GenerationDone => {
+                *solver = init_solver(board);
+            }
+            State::Generate => {
+                generator.draw(board, renderer);
+                if !step_by_step || *step {
+                    STATE = generator.step(board);
+                    STEP += 1;
+                    *step = false;
+                }
+                while FAST && STATE == State::Generate {
+                    if !step_by_step || *step {
+                        STATE = generator.step(board);
+                        STEP += 1;
+                        *step = false;
+                    }
+                }
+                if STATE == State::GenerationDone {
+                    generated(board);
+                }
+            }
+            State::Solve => {
+                solver.draw(board, renderer);
+                if let Some(error) = error {
+                    raylib::DrawText(
+                        CString::new(error.as_str()).expect("cstr").as_ptr(),
+                        (SCREEN_WIDTH - 350) as i32,
+                        710,
+                        24,
+                        raylib::RED,
+                    );
+                } else if !step_by_step || *step {
+                    match solver.step(board) {
+                        Ok(state) => {
+                            STATE = state;
+                            STEP += 1;
+                            *step = false;
+                        }
+                        Err(str) => *error = Some(str),
+                    }
+
+                    while FAST && STATE == State::Solve && error.is_none() {
+                        match solver.step(board) {
+                            Ok(state) => {
+                                STATE = state;
+                                STEP += 1;
+                                *step = false;
+                            }
+                            Err(str) => *error = Some(str),
+                        }
+                    }
+                }
+            }
+            State::Done => board.draw_path(solver.get_path(), &Style::default(), renderer),
         }
     }
 }
 
-fn init_maze(cell_count: usize, cell_size: usize) -> (Board, Box<dyn Generator>, Box<dyn Solver>) {
+#[allow(static_mut_refs)]
+fn init_maze(
+    width: usize,
+    height: usize,
+    cell_size: usize,
+) -> (Board, Box<dyn Generator>, Box<dyn Solver>) {
     unsafe {
-        let mut board = Board::new(BORDER, cell_count, cell_size);
+        let mut board = match &MASK {
+            Some(mask) if mask.width == width && mask.height == height => {
+                Board::with_mask(BORDER, mask, cell_size).expect("the mask was checked")
+            }
+            _ => Board::with_wrap(
+                BORDER,
+                width,
+                height,
+                cell_size,
+                WRAPS
+                    .get(SELECTED_WRAP as usize)
+                    .copied()
+                    .unwrap_or_default(),
+            ),
+        };
+        let seed: u64 = rand::random();
+        SEED = seed;
         let solver = init_solver(&board);
-        let generator: Box<dyn Generator> = match SELECTED_GENERATOR {
-            0 => Box::new(Backtracking::new()),
-            1 => Box::new(Kruskal::new(&board)),
-            2 => Box::new(Eller::new(&board)),
-            3 => Box::new(Prim::new(&board)),
-            4 => Box::new(RecursiveDivision::new(&mut board)),
-            5 => Box::new(AldousBroder::new(&board)),
-            6 => Box::new(Wilson::new(&mut board)),
-            7 => Box::new(HuntAndKill::new(&mut board)),
-            8 => Box::new(GrowingTree::new(&board)),
-            9 => Box::new(BinaryTree::new()),
-            10 => Box::new(Sidewinder::new(&mut board)),
-            _ => panic!(),
+        let generator = generator::by_name(
+            generator::ALGORITHMS[SELECTED_GENERATOR as usize],
+            &mut board,
+            seed,
+        )
+        .unwrap();
+        GRID = match SELECTED_WRAP {
+            LAYERED_MODE => Some(init_grid(
+                LayeredBoard::new(BORDER, width, height, LEVELS, cell_size),
+                seed,
+            )),
+            POLAR_MODE => Some(init_grid(PolarBoard::new(BORDER, height, cell_size), seed)),
+            DELTA_MODE => Some(init_grid(
+                DeltaBoard::new(BORDER, width, height, cell_size),
+                seed,
+            )),
+            _ => None,
         };
         STATE = State::Wait;
         STEP = 0;
@@ -66,8 +318,26 @@ fn init_maze(cell_count: usize, cell_size: usize) -> (Board, Box<dyn Generator>,
 fn main() {
     unsafe {
         // initialize the maze
-        let mut cell_count = 5;
-        let mut cell_size = (SCREEN_HEIGHT - 2 * BORDER) / cell_count;
+        let mut width = 5;
+        let mut height = 5;
+        // an optional mask file gives the maze its shape
+        if let Some(file) = std::env::args().nth(1) {
+            match Mask::load(&file).and_then(|mask| {
+                Board::with_mask(BORDER, &mask, 1)?;
+                Ok(mask)
+            }) {
+                Ok(mask) => {
+                    width = mask.width;
+                    height = mask.height;
+                    MASK = Some(mask);
+                }
+                Err(err) => {
+                    eprintln!("{err}");
+                    std::process::exit(1);
+                }
+            }
+        }
+        let mut cell_size = fit_cell_size(width, height);
         let mut step_by_step = false;
         let mut step = false;
         let mut error: Option<String> = None;
@@ -75,7 +345,7 @@ fn main() {
         let mut slider = 0;
 
         let mut text_buffer: Vec<u8> = vec![20; 0];
-        text_buffer.extend_from_slice(format!("   {}", cell_count).as_bytes());
+        text_buffer.extend_from_slice(format!("   {}", width).as_bytes());
 
         raylib::InitWindow(
             SCREEN_WIDTH as i32,
@@ -92,7 +362,8 @@ fn main() {
             18,
         );
 
-        let (mut board, mut generator, mut solver) = init_maze(cell_count, cell_size);
+        let (mut board, mut generator, mut solver) = init_maze(width, height, cell_size);
+        let mut raylib_renderer = RaylibRenderer;
 
         // main loop
         while !raylib::WindowShouldClose() {
@@ -108,6 +379,23 @@ fn main() {
                 raylib::WHITE,
             );
 
+            let mut new_wrap = SELECTED_WRAP;
+            raygui::GuiComboBox(
+                raylib::Rectangle {
+                    x: SCREEN_WIDTH as f32 - 270.0,
+                    y: 65.0,
+                    width: 220.0,
+                    height: 30.0,
+                },
+                str!("no wrap;cylinder;torus;layered;polar;delta"),
+                &mut new_wrap,
+            );
+            if new_wrap != SELECTED_WRAP {
+                SELECTED_WRAP = new_wrap;
+                cell_size = fit_cell_size(width, height);
+                (board, generator, solver) = init_maze(width, height, cell_size);
+            }
+
             let mut new_slider = slider;
             raygui::GuiComboBox(
                 raylib::Rectangle {
@@ -116,29 +404,39 @@ fn main() {
                     width: 300.0,
                     height: 30.0,
                 },
-                str!("5x5;10x10;20x20;30x30;40x40;50x50;60x60;70x70;80x80;90x90;100x100;1000x1000"),
+                str!(
+                    "5x5;10x10;20x20;30x30;40x40;50x50;60x60;70x70;80x80;90x90;100x100;1000x1000;10x5;20x10;40x20;80x40;160x80;21x7;63x21"
+                ),
                 &mut new_slider,
             );
             if new_slider != slider {
-                let new_count = match new_slider {
-                    0 => 5,
-                    1 => 10,
-                    2 => 20,
-                    3 => 30,
-                    4 => 40,
-                    5 => 50,
-                    6 => 60,
-                    7 => 70,
-                    8 => 80,
-                    9 => 90,
-                    10 => 100,
-                    _ => 200,
+                let (new_width, new_height) = match new_slider {
+                    0 => (5, 5),
+                    1 => (10, 10),
+                    2 => (20, 20),
+                    3 => (30, 30),
+                    4 => (40, 40),
+                    5 => (50, 50),
+                    6 => (60, 60),
+                    7 => (70, 70),
+                    8 => (80, 80),
+                    9 => (90, 90),
+                    10 => (100, 100),
+                    11 => (1000, 1000),
+                    12 => (10, 5),
+                    13 => (20, 10),
+                    14 => (40, 20),
+                    15 => (80, 40),
+                    16 => (160, 80),
+                    17 => (21, 7),
+                    18 => (63, 21),
+                    _ => unreachable!(),
                 };
-                let new_cell_size = (SCREEN_HEIGHT - 2 * BORDER) / new_count;
                 slider = new_slider;
-                cell_count = new_count;
-                cell_size = new_cell_size;
-                (board, generator, solver) = init_maze(cell_count, cell_size);
+                width = new_width;
+                height = new_height;
+                cell_size = fit_cell_size(width, height);
+                (board, generator, solver) = init_maze(width, height, cell_size);
             }
 
             raylib::DrawText(
@@ -157,14 +455,14 @@ fn main() {
                     width: 300.0,
                     height: 30.0,
                 },
-                str!(
-                    "recursive backtracker;kruskal;eller;prim;recursive division;aldous broder;wilson;hunt and kill;growing tree;binary tree;sidewinder"
-                ),
+                CString::new(generator::ALGORITHMS.join(";"))
+                    .expect("cstr")
+                    .as_ptr(),
                 &mut new_generator,
             );
             if new_generator != SELECTED_GENERATOR {
                 SELECTED_GENERATOR = new_generator;
-                (board, generator, solver) = init_maze(cell_count, cell_size);
+                (board, generator, solver) = init_maze(width, height, cell_size);
             }
 
             raylib::DrawText(
@@ -183,12 +481,17 @@ fn main() {
                     width: 300.0,
                     height: 30.0,
                 },
-                str!("djikstra;recursive backtracker;a*;dead end filling"),
+                CString::new(solver::SOLVERS.join(";"))
+                    .expect("cstr")
+                    .as_ptr(),
                 &mut new_solver,
             );
             if new_solver != SELECTED_SOLVER {
                 SELECTED_SOLVER = new_solver;
                 solver = init_solver(&board);
+                if let Some(grid) = &mut GRID {
+                    grid.reset_solver();
+                }
                 STATE = State::Wait;
             }
 
@@ -202,7 +505,7 @@ fn main() {
                 CString::new("generate").expect("cstr").as_ptr(),
             ) != 0
             {
-                (board, generator, solver) = init_maze(cell_count, cell_size);
+                (board, generator, solver) = init_maze(width, height, cell_size);
                 step_by_step = false;
                 STATE = State::Generate;
                 STEP = 0;
@@ -219,6 +522,9 @@ fn main() {
             {
                 error = None;
                 solver = init_solver(&board);
+                if let Some(grid) = &mut GRID {
+                    grid.reset_solver();
+                }
                 STATE = State::Solve;
                 STEP = 0;
             }
@@ -258,7 +564,7 @@ fn main() {
                 CString::new("reset").expect("cstr").as_ptr(),
             ) != 0
             {
-                (board, generator, solver) = init_maze(cell_count, cell_size);
+                (board, generator, solver) = init_maze(width, height, cell_size);
             }
 
             raygui::GuiCheckBox(
@@ -272,6 +578,121 @@ fn main() {
                 &FAST,
             );
 
+            if raygui::GuiButton(
+                raylib::Rectangle {
+                    x: SCREEN_WIDTH as f32 - 190.0,
+                    y: 380.0,
+                    width: 65.0,
+                    height: 30.0,
+                },
+                CString::new("svg").expect("cstr").as_ptr(),
+            ) != 0
+            {
+                let done = STATE == State::Done;
+                let result = if let Some(grid) = &GRID {
+                    grid.write_svg(done)
+                } else {
+                    let solution = done.then(|| solver.get_path().as_slice());
+                    svg::write_svg("maze.svg", &board, solution, &SvgOptions::default())
+                };
+                if let Err(err) = result {
+                    eprintln!("svg export failed: {err}");
+                }
+            }
+            if raygui::GuiButton(
+                raylib::Rectangle {
+                    x: SCREEN_WIDTH as f32 - 115.0,
+                    y: 380.0,
+                    width: 65.0,
+                    height: 30.0,
+                },
+                CString::new("png").expect("cstr").as_ptr(),
+            ) != 0
+            {
+                let solving = STATE == State::Solve || STATE == State::Done;
+                let result = if let Some(grid) = &GRID {
+                    grid.write_png(solving)
+                } else {
+                    let solver = solving.then(|| solver.as_ref());
+                    raster::write_png("maze.png", &board, solver, &RasterOptions::default())
+                };
+                if let Err(err) = result {
+                    eprintln!("png export failed: {err}");
+                }
+            }
+
+            if raygui::GuiButton(
+                raylib::Rectangle {
+                    x: SCREEN_WIDTH as f32 - 350.0,
+                    y: 420.0,
+                    width: 140.0,
+                    height: 30.0,
+                },
+                CString::new("save").expect("cstr").as_ptr(),
+            ) != 0
+            {
+                let metadata = Metadata {
+                    algorithm: generator::ALGORITHMS[SELECTED_GENERATOR as usize].to_string(),
+                    seed: Some(SEED),
+                };
+                if GRID.is_some() {
+                    eprintln!("save failed: only the flat board can be saved");
+                } else if let Err(err) = SavedMaze::new(&board, metadata).save(SAVE_FILE) {
+                    eprintln!("save failed: {err}");
+                }
+            }
+            if raygui::GuiButton(
+                raylib::Rectangle {
+                    x: SCREEN_WIDTH as f32 - 190.0,
+                    y: 420.0,
+                    width: 140.0,
+                    height: 30.0,
+                },
+                CString::new("load").expect("cstr").as_ptr(),
+            ) != 0
+            {
+                match SavedMaze::load(SAVE_FILE).and_then(|saved| Ok((saved.to_board()?, saved))) {
+                    Ok((loaded, saved)) => {
+                        width = loaded.width;
+                        height = loaded.height;
+                        SELECTED_WRAP = WRAPS
+                            .iter()
+                            .position(|wrap| *wrap == loaded.wrap)
+                            .unwrap_or_default() as i32;
+                        cell_size = fit_cell_size(width, height);
+                        (board, generator, solver) = init_maze(width, height, cell_size);
+                        board.cells = loaded.cells;
+                        board.start = loaded.start;
+                        board.goal = loaded.goal;
+                        if let Some(index) = generator::ALGORITHMS
+                            .iter()
+                            .position(|name| *name == saved.metadata.algorithm)
+                        {
+                            SELECTED_GENERATOR = index as i32;
+                        }
+                        SEED = saved.metadata.seed.unwrap_or_default();
+                        STATE = State::GenerationDone;
+                    }
+                    Err(err) => eprintln!("load failed: {err}"),
+                }
+            }
+
+            raygui::GuiSlider(
+                raylib::Rectangle {
+                    x: SCREEN_WIDTH as f32 - 270.0,
+                    y: 460.0,
+                    width: 160.0,
+                    height: 30.0,
+                },
+                CString::new("braid").expect("cstr").as_ptr(),
+                CString::new(format!("{:.2}", BRAID))
+                    .expect("cstr")
+                    .as_ptr(),
+                &BRAID,
+                0.0,
+                1.0,
+            );
+
             // Displaz the state and other info
 
             raylib::DrawText(
@@ -279,17 +700,17 @@ fn main() {
                     .expect("cstr")
                     .as_ptr(),
                 (SCREEN_WIDTH - 350) as i32,
-                440,
+                520,
                 24,
                 raylib::WHITE,
             );
 
             raylib::DrawText(
-                CString::new(format!("Size: {}x{}", cell_count, cell_count))
+                CString::new(format!("Size: {}x{}", width, height))
                     .expect("cstr")
                     .as_ptr(),
                 (SCREEN_WIDTH - 350) as i32,
-                470,
+                550,
                 24,
                 raylib::WHITE,
             );
@@ -299,79 +720,104 @@ fn main() {
                     .expect("cstr")
                     .as_ptr(),
                 (SCREEN_WIDTH - 350) as i32,
-                500,
+                580,
                 24,
                 raylib::WHITE,
             );
 
+            let (path, expanded) = match &GRID {
+                Some(grid) => grid.solution(),
+                None => (solver.get_path().as_slice(), solver.expanded()),
+            };
             raylib::DrawText(
-                CString::new(format!("Solution length: {}", solver.get_path().len()))
+                CString::new(format!("Solution length: {}", path.len()))
                     .expect("cstr")
                     .as_ptr(),
                 (SCREEN_WIDTH - 350) as i32,
-                530,
+                610,
                 24,
                 raylib::WHITE,
             );
 
-            // draw the board
-            board.draw();
+            if let Some(expanded) = expanded {
+                raylib::DrawText(
+                    CString::new(format!("Expanded: {}", expanded))
+                        .expect("cstr")
+                        .as_ptr(),
+                    (SCREEN_WIDTH - 350) as i32,
+                    640,
+                    24,
+                    raylib::WHITE,
+                );
+            }
 
-            match STATE {
-                State::Wait => {}
-                State::GenerationDone => {
-                    solver = init_solver(&board);
-                }
-                State::Generate => {
-                    generator.draw(&board);
-                    if !step_by_step || step {
-                        STATE = generator.step(&mut board);
-                        STEP += 1;
-                        step = false;
-                    }
-                    while FAST && STATE == State::Generate {
-                        if !step_by_step || step {
-                            STATE = generator.step(&mut board);
-                            STEP += 1;
-                            step = false;
-                        }
-                    }
-                }
-                State::Solve => {
-                    solver.draw(&board);
-                    if let Some(error) = &error {
-                        raylib::DrawText(
-                            CString::new(error.as_str()).expect("cstr").as_ptr(),
-                            (SCREEN_WIDTH - 350) as i32,
-                            600,
-                            24,
-                            raylib::RED,
-                        );
-                    } else if !step_by_step || step {
-                        match solver.step(&board) {
-                            Ok(state) => {
-                                STATE = state;
-                                STEP += 1;
-                                step = false;
-                            }
-                            Err(str) => error = Some(str),
-                        }
+            raylib::DrawText(
+                CString::new(format!("Seed: {}", SEED))
+                    .expect("cstr")
+                    .as_ptr(),
+                (SCREEN_WIDTH - 350) as i32,
+                670,
+                24,
+                raylib::WHITE,
+            );
 
-                        while FAST && STATE == State::Solve && error.is_none() {
-                            match solver.step(&board) {
-                                Ok(state) => {
-                                    STATE = state;
-                                    STEP += 1;
-                                    step = false;
-                                }
-                                Err(str) => error = Some(str),
-                            }
-                        }
+            let scale = fit_scale(width, height);
+            let origin = raylib::Vector2::new(BORDER as f32, BORDER as f32);
+            let mut renderer = Scaled {
+                renderer: &mut raylib_renderer,
+                origin,
+                scale,
+            };
+
+            if let Some(grid) = &mut GRID {
+                // the levels of a layered board side by side with their
+                // stairs, the rings of a polar one or the triangles
+                grid.frame(step_by_step, &mut step, &mut error, &mut renderer);
+                raylib::EndDrawing();
+                continue;
+            }
+
+            // draw the board
+            board.draw(&mut renderer);
+
+            // left click moves the start, right click the goal
+            if STATE == State::GenerationDone || STATE == State::Done {
+                let mouse = raylib::GetMousePosition();
+                let mouse = raylib::Vector2::new(
+                    origin.x + (mouse.x - origin.x) / scale,
+                    origin.y + (mouse.y - origin.y) / scale,
+                );
+                let left = raylib::IsMouseButtonPressed(MouseButton::Left);
+                let right = raylib::IsMouseButtonPressed(MouseButton::Right);
+                if let Some(index) = board.cell_at(mouse.x, mouse.y)
+                    && (left || right)
+                {
+                    if left {
+                        board.set_start(index);
+                    } else {
+                        board.set_goal(index);
                     }
+                    error = None;
+                    STATE = State::GenerationDone;
                 }
-                State::Done => path::draw_path(&board, solver.get_path()),
             }
 
+            update(
+                &mut board,
+                generator.as_mut(),
+                &mut solver,
+                init_solver,
+                |board| {
+                    if BRAID > 0.0 {
+                        braid::braid_with_seed(board, BRAID as f64, SEED);
+                    }
+                },
+                step_by_step,
+                &mut step,
+                &mut error,
+                &mut renderer,
+            );
+
             raylib::EndDrawing();
         }
         raylib::CloseWindow();
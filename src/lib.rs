@@ -1,3 +1,4 @@
 pub mod maze;
+#[cfg(feature = "raylib")]
 pub mod raygui;
 pub mod raylib;
@@ -0,0 +1,345 @@
+use crate::maze::{Board, topology::Topology};
+
+/// Simple paths from the start to the goal of a board.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Solutions {
+    /// number of paths found
+    pub count: usize,
+    /// the search stopped at the cap, there may be more paths
+    pub capped: bool,
+    /// the paths themselves, only filled by `list_solutions`
+    pub paths: Vec<Vec<usize>>,
+}
+
+/**
+ * Depth first search over all simple paths from the start to the goal,
+ * stopping after `cap` paths. The search keeps its own stack so long
+ * corridors do not overflow the call stack.
+ */
+fn search<T: Topology>(board: &T, cap: usize, list: bool) -> Solutions {
+    let mut solutions = Solutions::default();
+    if cap == 0 {
+        solutions.capped = true;
+        return solutions;
+    }
+    if board.start() == board.goal() {
+        solutions.count = 1;
+        if list {
+            solutions.paths.push(vec![board.start()]);
+        }
+        return solutions;
+    }
+
+    let mut on_path = vec![false; board.cell_count()];
+    on_path[board.start()] = true;
+    let mut path = vec![board.start()];
+    // the passages still to try from every cell on the path
+    let mut options = vec![board.passages(board.start())];
+    while let Some(last) = options.last_mut() {
+        let Some(next) = last.pop() else {
+            options.pop();
+            on_path[path.pop().unwrap()] = false;
+            continue;
+        };
+        if on_path[next] {
+            continue;
+        }
+        if next == board.goal() {
+            solutions.count += 1;
+            if list {
+                let mut solution = path.clone();
+                solution.push(next);
+                solutions.paths.push(solution);
+            }
+            if solutions.count >= cap {
+                solutions.capped = true;
+                break;
+            }
+            continue;
+        }
+        on_path[next] = true;
+        path.push(next);
+        options.push(board.passages(next));
+    }
+    solutions
+}
+
+/**
+ * Count the simple paths from the start to the goal, up to `cap`.
+ */
+pub fn count_solutions<T: Topology>(board: &T, cap: usize) -> Solutions {
+    search(board, cap, false)
+}
+
+/**
+ * List the simple paths from the start to the goal, up to `cap`.
+ */
+pub fn list_solutions<T: Topology>(board: &T, cap: usize) -> Solutions {
+    search(board, cap, true)
+}
+
+/**
+ * Whether there is exactly one path between any two cells: all cells are
+ * connected and there are no loops, so the passages form a spanning tree.
+ * Masked cells are not part of the maze.
+ */
+pub fn is_perfect<T: Topology>(board: &T) -> bool {
+    let enabled = board.enabled_cells();
+    let Some(&first) = enabled.first() else {
+        return false;
+    };
+    let passages: usize = enabled
+        .iter()
+        .map(|&index| board.passages(index).len())
+        .sum();
+    // every passage is seen from both of its cells
+    if passages / 2 != enabled.len() - 1 {
+        return false;
+    }
+
+    let mut reached = vec![false; board.cell_count()];
+    reached[first] = true;
+    let mut stack = vec![first];
+    let mut count = 1;
+    while let Some(cell) = stack.pop() {
+        for neighbor in board.passages(cell) {
+            if !reached[neighbor] {
+                reached[neighbor] = true;
+                count += 1;
+                stack.push(neighbor);
+            }
+        }
+    }
+    count == enabled.len()
+}
+
+/// Numbers to compare mazes made by different generators.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Statistics {
+    pub cells: usize,
+    /// cells with exactly one passage
+    pub dead_ends: usize,
+    /// cells with two opposite passages
+    pub straights: usize,
+    /// cells with two passages at a right angle
+    pub turns: usize,
+    /// share of the corridor cells (two passages) that go straight
+    pub straight_share: f64,
+    /// share of the corridor cells that turn
+    pub turn_share: f64,
+    /// average number of steps between two cells that are not a plain
+    /// corridor, i.e. dead ends and junctions. Higher means long winding
+    /// passages, lower means many short branches.
+    pub river: f64,
+    /// number of steps of the longest shortest path, exact for perfect
+    /// mazes and a lower bound otherwise
+    pub diameter: usize,
+    /// the two ends of that path
+    pub diameter_ends: (usize, usize),
+    /// number of steps from the start to the goal, if it is reachable
+    pub solution_length: Option<usize>,
+    /// solution length divided by the number of cells
+    pub solution_ratio: f64,
+    /// number of cells with 0, 1, 2, 3 and 4 passages
+    pub branching: [usize; 5],
+}
+
+/**
+ * Breadth first search from `from`, the number of steps to every cell.
+ */
+pub fn distances<T: Topology>(board: &T, from: usize) -> Vec<Option<usize>> {
+    let mut distance = vec![None; board.cell_count()];
+    distance[from] = Some(0);
+    let mut frontier = vec![from];
+    let mut depth = 0;
+    while !frontier.is_empty() {
+        depth += 1;
+        let mut next = vec![];
+        for cell in frontier {
+            for neighbor in board.passages(cell) {
+                if distance[neighbor].is_none() {
+                    distance[neighbor] = Some(depth);
+                    next.push(neighbor);
+                }
+            }
+        }
+        frontier = next;
+    }
+    distance
+}
+
+/// The cell furthest away from `from` and its distance.
+fn furthest<T: Topology>(board: &T, from: usize) -> (usize, usize) {
+    distances(board, from)
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, distance)| distance.map(|distance| (index, distance)))
+        .max_by_key(|&(_, distance)| distance)
+        .unwrap_or((from, 0))
+}
+
+pub fn statistics(board: &Board) -> Statistics {
+    let mut stats = Statistics {
+        cells: board.enabled_cells().len(),
+        ..Statistics::default()
+    };
+
+    let passages: Vec<Vec<usize>> = (0..board.cells.len())
+        .map(|index| board.passages(index))
+        .collect();
+    for (index, open) in passages.iter().enumerate() {
+        if board.cells[index].masked {
+            continue;
+        }
+        stats.branching[open.len()] += 1;
+        match open.as_slice() {
+            [_] => stats.dead_ends += 1,
+            [a, b] => {
+                let cell = &board.cells[index];
+                let (a, b) = (&board.cells[*a], &board.cells[*b]);
+                if (a.x == cell.x && b.x == cell.x) || (a.y == cell.y && b.y == cell.y) {
+                    stats.straights += 1;
+                } else {
+                    stats.turns += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    let corridors = stats.straights + stats.turns;
+    if corridors > 0 {
+        stats.straight_share = stats.straights as f64 / corridors as f64;
+        stats.turn_share = stats.turns as f64 / corridors as f64;
+    }
+
+    // follow every corridor from its ends, each one is walked from both
+    // sides which does not change the average
+    let (mut steps, mut segments) = (0, 0);
+    for (index, open) in passages.iter().enumerate() {
+        if open.len() == 2 || board.cells[index].masked {
+            continue;
+        }
+        for &first in open {
+            let (mut previous, mut current, mut length) = (index, first, 1);
+            while passages[current].len() == 2 {
+                let next = passages[current]
+                    .iter()
+                    .copied()
+                    .find(|&next| next != previous)
+                    .unwrap();
+                (previous, current) = (current, next);
+                length += 1;
+            }
+            steps += length;
+            segments += 1;
+        }
+    }
+    if segments > 0 {
+        stats.river = steps as f64 / segments as f64;
+    }
+
+    let (a, _) = furthest(board, board.start);
+    let (b, diameter) = furthest(board, a);
+    stats.diameter = diameter;
+    stats.diameter_ends = (a, b);
+
+    stats.solution_length = distances(board, board.start)[board.goal];
+    if let Some(length) = stats.solution_length {
+        stats.solution_ratio = length as f64 / stats.cells as f64;
+    }
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::{CELL_SIZE, Wrap, generator, headless};
+
+    #[test]
+    fn generators_are_perfect() {
+        for wrap in [Wrap::None, Wrap::Cylinder, Wrap::Torus] {
+            for name in generator::ALGORITHMS {
+                let mut board = Board::with_wrap(0, 11, 8, CELL_SIZE, wrap);
+                let mut generator = generator::by_name(name, &mut board, 11).unwrap();
+                headless::run(&mut board, generator.as_mut());
+                assert!(is_perfect(&board), "{name} {wrap:?}");
+                let solutions = count_solutions(&board, 2);
+                assert_eq!(solutions.count, 1, "{name} {wrap:?}");
+                assert!(!solutions.capped, "{name} {wrap:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn loops_and_islands() {
+        // a 2x2 board with every inner wall gone has two ways around
+        let mut board = Board::new(0, 2, 2, CELL_SIZE);
+        let cells = [(0, 0), (1, 0), (1, 1), (0, 1)].map(|(x, y)| board.get_index(x, y));
+        for pair in cells.windows(2) {
+            board.remove_wall(pair[0], pair[1]);
+        }
+        assert!(is_perfect(&board));
+        board.remove_wall(cells[3], cells[0]);
+        assert!(!is_perfect(&board));
+
+        let solutions = list_solutions(&board, 10);
+        assert_eq!(solutions.count, 2);
+        assert!(!solutions.capped);
+        assert!(
+            solutions
+                .paths
+                .iter()
+                .all(|path| path.first() == Some(&board.start) && path.last() == Some(&board.goal))
+        );
+        let capped = count_solutions(&board, 1);
+        assert_eq!((capped.count, capped.capped), (1, true));
+
+        // an unreachable cell makes it a forest
+        let board = Board::new(0, 2, 2, CELL_SIZE);
+        assert!(!is_perfect(&board));
+        assert_eq!(count_solutions(&board, 10).count, 0);
+    }
+
+    #[test]
+    fn statistics_of_known_boards() {
+        // a straight corridor from the start to the goal
+        let mut board = Board::new(0, 3, 1, CELL_SIZE);
+        board.remove_wall(0, 1);
+        board.remove_wall(1, 2);
+        let stats = statistics(&board);
+        assert_eq!(stats.cells, 3);
+        assert_eq!(stats.dead_ends, 2);
+        assert_eq!((stats.straights, stats.turns), (1, 0));
+        assert_eq!((stats.straight_share, stats.turn_share), (1.0, 0.0));
+        assert_eq!(stats.river, 2.0);
+        assert_eq!(stats.diameter, 2);
+        assert_eq!(stats.solution_length, Some(2));
+        assert_eq!(stats.solution_ratio, 2.0 / 3.0);
+        assert_eq!(stats.branching, [0, 2, 1, 0, 0]);
+
+        // a u from the top left round to the bottom left, the goal in the bend
+        let mut board = Board::new(0, 2, 2, CELL_SIZE);
+        let cells = [(0, 0), (1, 0), (1, 1), (0, 1)].map(|(x, y)| board.get_index(x, y));
+        for pair in cells.windows(2) {
+            board.remove_wall(pair[0], pair[1]);
+        }
+        let stats = statistics(&board);
+        assert_eq!(stats.dead_ends, 2);
+        assert_eq!((stats.straights, stats.turns), (0, 2));
+        assert_eq!(stats.river, 3.0);
+        assert_eq!(stats.diameter, 3);
+        assert_eq!(stats.diameter_ends, (cells[3], cells[0]));
+        assert_eq!(stats.solution_length, Some(2));
+        assert_eq!(stats.branching, [0, 2, 2, 0, 0]);
+    }
+
+    #[test]
+    fn statistics_without_passages() {
+        let board = Board::new(0, 3, 2, CELL_SIZE);
+        let stats = statistics(&board);
+        assert_eq!(stats.branching, [6, 0, 0, 0, 0]);
+        assert_eq!((stats.dead_ends, stats.diameter, stats.river), (0, 0, 0.0));
+        assert_eq!(stats.solution_length, None);
+        assert_eq!(stats.solution_ratio, 0.0);
+    }
+}
@@ -0,0 +1,336 @@
+use std::{fmt, str::FromStr};
+
+use crate::maze::{Board, CELL_SIZE, Direction, Wrap};
+
+// +---+---+---+
+//   S         |
+// +   +---+   +
+// |   |###|   |
+// +   +---+   +
+// |         G
+// +---+---+---+
+//
+// every cell is 4 characters wide and 2 lines high, the corners are always
+// `+`. A shared wall is written once, when either side of it is set. `S` and
+// `G` mark the start and the goal, `###` a masked cell. Without the marks
+// the first and the last opening in the outer wall are used. A wrapped board
+// ends with a `wrap: cylinder` or `wrap: torus` line, its seams are written
+// on both edges.
+
+const MASKED: &str = "###";
+
+fn wrap_name(wrap: Wrap) -> Option<&'static str> {
+    match wrap {
+        Wrap::None => None,
+        Wrap::Cylinder => Some("cylinder"),
+        Wrap::Torus => Some("torus"),
+    }
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let cell = &self.cells[self.get_index(x, y)];
+                let wall =
+                    cell.walls.top || (y > 0 && self.cells[self.get_index(x, y - 1)].walls.bottom);
+                write!(f, "+{}", if wall { "---" } else { "   " })?;
+            }
+            writeln!(f, "+")?;
+            for x in 0..self.width {
+                let cell = &self.cells[self.get_index(x, y)];
+                let wall =
+                    cell.walls.left || (x > 0 && self.cells[self.get_index(x - 1, y)].walls.right);
+                let index = self.get_index(x, y);
+                let inside = if cell.masked {
+                    MASKED
+                } else if index == self.start {
+                    " S "
+                } else if index == self.goal {
+                    " G "
+                } else {
+                    "   "
+                };
+                write!(f, "{}{}", if wall { '|' } else { ' ' }, inside)?;
+            }
+            let last = &self.cells[self.get_index(self.width - 1, y)];
+            writeln!(f, "{}", if last.walls.right { '|' } else { ' ' })?;
+        }
+        for x in 0..self.width {
+            let cell = &self.cells[self.get_index(x, self.height - 1)];
+            write!(f, "+{}", if cell.walls.bottom { "---" } else { "   " })?;
+        }
+        writeln!(f, "+")?;
+        if let Some(wrap) = wrap_name(self.wrap) {
+            writeln!(f, "wrap: {}", wrap)?;
+        }
+        Ok(())
+    }
+}
+
+fn horizontal(line: &[char], x: usize, row: usize) -> Result<bool, String> {
+    let segment: String = line[4 * x + 1..4 * x + 4].iter().collect();
+    match segment.as_str() {
+        "---" => Ok(true),
+        "   " => Ok(false),
+        _ => Err(format!(
+            "line {}: wall segment '{}' at column {} is neither open nor closed",
+            row + 1,
+            segment,
+            4 * x + 2
+        )),
+    }
+}
+
+fn vertical(line: &[char], x: usize, row: usize) -> Result<bool, String> {
+    match line[4 * x] {
+        '|' => Ok(true),
+        ' ' => Ok(false),
+        c => Err(format!(
+            "line {}: unexpected '{}' at column {}, expected '|' or ' '",
+            row + 1,
+            c,
+            4 * x + 1
+        )),
+    }
+}
+
+/// Cells with an open wall towards the outside of the maze, the edge of the
+/// board or a masked cell, once for every open side.
+fn openings(board: &Board) -> Vec<usize> {
+    let sides = [
+        Direction::North,
+        Direction::South,
+        Direction::West,
+        Direction::East,
+    ];
+    (0..board.cells.len())
+        .filter(|&index| !board.cells[index].masked)
+        .flat_map(|index| {
+            let walls = &board.cells[index].walls;
+            board
+                .neighbors(index)
+                .into_iter()
+                .zip(sides)
+                .filter(|&(neighbor, side)| neighbor.is_none() && !walls.has(side))
+                .map(move |_| index)
+        })
+        .collect()
+}
+
+impl FromStr for Board {
+    type Err = String;
+
+    /**
+     * Parse the format written by `Display`. Trailing whitespace may be
+     * missing, every other character has to match.
+     */
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<&str> = s
+            .lines()
+            .map(|line| line.trim_end())
+            .skip_while(|line| line.is_empty())
+            .collect();
+        let mut lines: Vec<&str> = match lines.iter().rposition(|line| !line.is_empty()) {
+            Some(last) => lines[..=last].to_vec(),
+            None => return Err(String::from("empty maze")),
+        };
+        let wrap = match lines.last().and_then(|line| line.strip_prefix("wrap:")) {
+            Some(wrap) => {
+                let wrap = match wrap.trim() {
+                    "cylinder" => Wrap::Cylinder,
+                    "torus" => Wrap::Torus,
+                    other => return Err(format!("unknown wrap '{}'", other)),
+                };
+                lines.pop();
+                wrap
+            }
+            None => Wrap::None,
+        };
+
+        let first = lines[0].chars().count();
+        if first < 5
+            || !(first - 1).is_multiple_of(4)
+            || lines.len() < 3
+            || lines.len().is_multiple_of(2)
+        {
+            return Err(format!(
+                "maze has to be 4 * width + 1 columns and 2 * height + 1 lines, got {}x{}",
+                first,
+                lines.len()
+            ));
+        }
+        let width = (first - 1) / 4;
+        let height = (lines.len() - 1) / 2;
+        let columns = 4 * width + 1;
+
+        let mut grid: Vec<Vec<char>> = Vec::new();
+        for (row, line) in lines.iter().enumerate() {
+            let mut chars: Vec<char> = line.chars().collect();
+            // marks anywhere but in the middle of a cell are not in the grid
+            if let Some(column) = chars.iter().enumerate().position(|(column, &c)| {
+                "SG".contains(c) && (row.is_multiple_of(2) || column % 4 != 2 || column >= columns)
+            }) {
+                return Err(format!(
+                    "line {}: mark '{}' at column {} is outside of the cells",
+                    row + 1,
+                    chars[column],
+                    column + 1
+                ));
+            }
+            // wall lines end in a corner, only the cell lines may lose their
+            // trailing whitespace
+            if chars.len() > columns || (row.is_multiple_of(2) && chars.len() != columns) {
+                return Err(format!(
+                    "line {}: expected {} columns, got {}",
+                    row + 1,
+                    columns,
+                    chars.len()
+                ));
+            }
+            chars.resize(columns, ' ');
+            if row.is_multiple_of(2) {
+                if let Some(x) = (0..=width).find(|x| chars[4 * x] != '+') {
+                    return Err(format!(
+                        "line {}: expected '+' at column {}",
+                        row + 1,
+                        4 * x + 1
+                    ));
+                }
+            } else if let Some(x) = (0..width).find(|x| {
+                let inside: String = chars[4 * x + 1..4 * x + 4].iter().collect();
+                !["   ", " S ", " G ", MASKED].contains(&inside.as_str())
+            }) {
+                return Err(format!("line {}: cell {} is not empty", row + 1, x + 1));
+            }
+            grid.push(chars);
+        }
+
+        let mut board = Board::with_wrap(0, width, height, CELL_SIZE, wrap);
+        let mut start = None;
+        let mut goal = None;
+        for y in 0..height {
+            for x in 0..width {
+                let index = board.get_index(x, y);
+                let mark = match grid[2 * y + 1][4 * x + 2] {
+                    'S' => Some((&mut start, "start")),
+                    'G' => Some((&mut goal, "goal")),
+                    _ => None,
+                };
+                if let Some((mark, name)) = mark
+                    && mark.replace(index).is_some()
+                {
+                    return Err(format!("more than one {} in the maze", name));
+                }
+                let cell = &mut board.cells[index];
+                cell.walls.top = horizontal(&grid[2 * y], x, 2 * y)?;
+                cell.walls.bottom = horizontal(&grid[2 * y + 2], x, 2 * y + 2)?;
+                cell.walls.left = vertical(&grid[2 * y + 1], x, 2 * y + 1)?;
+                cell.walls.right = vertical(&grid[2 * y + 1], x + 1, 2 * y + 1)?;
+                cell.masked = grid[2 * y + 1][4 * x + 1] == '#';
+                cell.visited = !cell.masked;
+            }
+        }
+        // the seams of a wrapped board are written on both edges
+        board.check_walls()?;
+
+        let openings = openings(&board);
+        if openings.len() > 2 {
+            return Err(format!(
+                "the outer wall has {} openings, only the entrance and the exit may be open",
+                openings.len()
+            ));
+        }
+        board.start = start.or(openings.first().copied()).unwrap_or(0);
+        board.goal = goal
+            .or(openings.last().copied().filter(|&last| last != board.start))
+            .unwrap_or(board.cells.len() - 1);
+        if board.cells[board.start].masked || board.cells[board.goal].masked {
+            return Err(String::from("the entrance or the exit is masked"));
+        }
+        Ok(board)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::{generator, headless, mask::Mask};
+
+    #[test]
+    fn round_trip() {
+        for name in generator::ALGORITHMS {
+            let mut board = Board::new(0, 7, 4, CELL_SIZE);
+            let mut generator = generator::by_name(name, &mut board, 3).unwrap();
+            headless::run(&mut board, generator.as_mut());
+            let parsed: Board = board.to_string().parse().unwrap();
+            assert_eq!(parsed, board, "{name}");
+        }
+    }
+
+    #[test]
+    fn round_trip_wrap_and_mask() {
+        let mut mask = Mask::new(7, 4);
+        mask.set(3, 1, false);
+        mask.set(3, 2, false);
+        let boards = [
+            Board::with_wrap(0, 7, 4, CELL_SIZE, Wrap::Cylinder),
+            Board::with_wrap(0, 7, 4, CELL_SIZE, Wrap::Torus),
+            Board::with_mask(0, &mask, CELL_SIZE).unwrap(),
+        ];
+        for mut board in boards {
+            let mut generator = generator::by_name("wilson", &mut board, 3).unwrap();
+            headless::run(&mut board, generator.as_mut());
+            let text = board.to_string();
+            assert_eq!(text.parse::<Board>().unwrap(), board, "{text}");
+        }
+    }
+
+    #[test]
+    fn example() {
+        let board: Board = "\
++---+---+---+
+  S         |
++   +---+   +
+|   |###|   |
++   +---+   +
+|         G
++---+---+---+
+"
+        .parse()
+        .unwrap();
+        assert_eq!(board.start, board.get_index(0, 0));
+        assert_eq!(board.goal, board.get_index(2, 2));
+        assert!(board.cells[board.get_index(1, 1)].masked);
+        assert!(!board.cells[board.goal].walls.right);
+    }
+
+    #[test]
+    fn malformed() {
+        for text in [
+            "",
+            "+---+\n",
+            "+---+\n|   |\n",
+            "+---+\n| x |\n+---+\n",
+            "+---+\n|   |\n+-- +\n",
+            "+---+\n|   |\n+---+---+\n",
+            "+---+---+\n|   |   |\n+---+\n",
+            // ragged
+            "+---+---+\n|   |   |  |\n+---+---+\n",
+            // missing border
+            "+   +   +\n|       |\n+   +   +\n",
+            // marks outside of the cells
+            "+---+-S-+\n|       |\n+---+---+\n",
+            "+---+---+\n|   S   |\n+---+---+\n",
+            "+---+---+\n|       |  G\n+---+---+\n",
+            "+---+---+\n| S   S |\n+---+---+\n",
+            // masked start
+            "+---+---+\n|###|   |\n+---+---+\n",
+            // the seam does not match
+            "+---+---+---+\n|       |    \n+---+---+---+\nwrap: cylinder\n",
+            "+---+\n|   |\n+---+\nwrap: sphere\n",
+        ] {
+            assert!(text.parse::<Board>().is_err(), "{text:?}");
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use rand::{Rng, SeedableRng, rngs::StdRng, seq::SliceRandom};
+
+use crate::maze::Board;
+
+/**
+ * Remove dead ends to add loops to a finished maze. Every dead end is
+ * opened with the given probability, 0 keeps the maze perfect and 1 removes
+ * all dead ends. A wall towards another dead end is preferred, that fixes
+ * two at once.
+ */
+pub fn braid(board: &mut Board, probability: f64) {
+    braid_with_seed(board, probability, rand::random())
+}
+
+pub fn braid_with_seed(board: &mut Board, probability: f64, seed: u64) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut dead_ends: Vec<usize> = (0..board.cells.len())
+        .filter(|&index| board.passages(index).len() == 1)
+        .collect();
+    dead_ends.shuffle(&mut rng);
+
+    for cell in dead_ends {
+        // an earlier wall may already have opened this one
+        if board.passages(cell).len() != 1 || !rng.random_bool(probability.clamp(0.0, 1.0)) {
+            continue;
+        }
+        let passages = board.passages(cell);
+        let closed: Vec<usize> = board
+            .neighbors(cell)
+            .into_iter()
+            .flatten()
+            .filter(|neighbor| !passages.contains(neighbor))
+            .collect();
+        let preferred: Vec<usize> = closed
+            .iter()
+            .copied()
+            .filter(|&neighbor| board.passages(neighbor).len() == 1)
+            .collect();
+        let candidates = if preferred.is_empty() {
+            closed
+        } else {
+            preferred
+        };
+        if candidates.is_empty() {
+            continue;
+        }
+        let neighbor = candidates[rng.random_range(0..candidates.len())];
+        board.remove_wall(cell, neighbor);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::{CELL_SIZE, Wrap, analysis, generator, headless};
+
+    #[test]
+    fn removes_every_dead_end() {
+        for wrap in [Wrap::None, Wrap::Torus] {
+            for name in generator::ALGORITHMS {
+                let mut board = Board::with_wrap(0, 10, 7, CELL_SIZE, wrap);
+                let mut generator = generator::by_name(name, &mut board, 2).unwrap();
+                headless::run(&mut board, generator.as_mut());
+                braid_with_seed(&mut board, 1.0, 2);
+                assert_eq!(analysis::statistics(&board).dead_ends, 0, "{name} {wrap:?}");
+                assert!(!analysis::is_perfect(&board), "{name} {wrap:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn zero_keeps_the_maze() {
+        let mut board = Board::new(0, 10, 7, CELL_SIZE);
+        let mut generator = generator::by_name("wilson", &mut board, 2).unwrap();
+        headless::run(&mut board, generator.as_mut());
+        let perfect = board.clone();
+        braid_with_seed(&mut board, 0.0, 2);
+        assert_eq!(board, perfect);
+    }
+}
@@ -0,0 +1,311 @@
+use crate::{
+    maze::{
+        render::{Renderer, Style},
+        topology::Topology,
+    },
+    raylib::{Rectangle, Vector2},
+};
+
+/// Number of sides of a triangle cell.
+pub const SIDES: usize = 3;
+
+/// The side facing the neighbor on the other side of a wall, for the walls
+/// in the order of `DeltaCell::walls`.
+const OPPOSITE: [usize; SIDES] = [1, 0, 2];
+
+#[derive(Clone, Debug)]
+pub struct DeltaCell {
+    pub x: usize,
+    pub y: usize,
+    pub visited: bool,
+    /// walls in the order left, right, base. The base is at the bottom of
+    /// cells pointing up and at the top of cells pointing down.
+    pub walls: [bool; SIDES],
+}
+
+impl DeltaCell {
+    pub fn new(x: usize, y: usize) -> Self {
+        Self {
+            x,
+            y,
+            visited: false,
+            walls: [true; SIDES],
+        }
+    }
+
+    pub fn points_up(&self) -> bool {
+        (self.x + self.y).is_multiple_of(2)
+    }
+}
+
+/// A grid of triangles that alternate between pointing up and down. Every
+/// cell shares its left and right side with the cells in the same row and
+/// its base with the cell above or below.
+#[derive(Clone, Debug)]
+pub struct DeltaBoard {
+    pub cells: Vec<DeltaCell>,
+    pub width: usize,
+    pub height: usize,
+    pub start: usize,
+    pub goal: usize,
+    /// length of a side
+    pub cell_size: usize,
+    pub x: usize,
+    pub y: usize,
+}
+
+impl DeltaBoard {
+    pub fn new(border: usize, width: usize, height: usize, cell_size: usize) -> Self {
+        let mut cells = Vec::with_capacity(width * height);
+        for x in 0..width {
+            for y in 0..height {
+                cells.push(DeltaCell::new(x, y));
+            }
+        }
+        let mut board = Self {
+            cells,
+            width,
+            height,
+            start: 0,
+            goal: width * height - 1,
+            cell_size,
+            x: border,
+            y: border,
+        };
+        // the entrance is on the left side, the exit on the right side
+        board.cells[board.start].walls[0] = false;
+        let goal = board.goal;
+        board.cells[goal].walls[1] = false;
+        board
+    }
+
+    pub fn get_index(&self, x: usize, y: usize) -> usize {
+        x * self.height + y
+    }
+
+    /**
+     * return the neighbors in the order of `DeltaCell::walls`
+     */
+    pub fn neighbors(&self, index: usize) -> [Option<usize>; SIDES] {
+        let cell = &self.cells[index];
+        let (x, y) = (cell.x, cell.y);
+        let base = if cell.points_up() {
+            (y + 1 < self.height).then(|| self.get_index(x, y + 1))
+        } else {
+            (y > 0).then(|| self.get_index(x, y - 1))
+        };
+        [
+            (x > 0).then(|| self.get_index(x - 1, y)),
+            (x + 1 < self.width).then(|| self.get_index(x + 1, y)),
+            base,
+        ]
+    }
+
+    /// The side of `cell` that faces `neighbor`.
+    fn side(&self, cell: usize, neighbor: usize) -> usize {
+        self.neighbors(cell)
+            .iter()
+            .position(|&other| other == Some(neighbor))
+            .expect("cells are not neighbors")
+    }
+
+    fn row_height(&self) -> f32 {
+        self.cell_size as f32 * 3f32.sqrt() / 2.0
+    }
+
+    /// The corners of the cell: the tip, then the left and right end of the
+    /// base.
+    fn corners(&self, cell: usize) -> [Vector2; 3] {
+        let cell = &self.cells[cell];
+        let size = self.cell_size as f32;
+        let left = self.x as f32 + cell.x as f32 * size / 2.0;
+        let top = self.y as f32 + cell.y as f32 * self.row_height();
+        let bottom = top + self.row_height();
+        let (tip, base) = if cell.points_up() {
+            (top, bottom)
+        } else {
+            (bottom, top)
+        };
+        [
+            Vector2::new(left + size / 2.0, tip),
+            Vector2::new(left, base),
+            Vector2::new(left + size, base),
+        ]
+    }
+
+    /**
+     * check that both sides of every shared wall agree
+     */
+    pub fn check_walls(&self) -> Result<(), String> {
+        for (index, cell) in self.cells.iter().enumerate() {
+            for (side, neighbor) in self.neighbors(index).into_iter().enumerate() {
+                if let Some(neighbor) = neighbor
+                    && cell.walls[side] != self.cells[neighbor].walls[OPPOSITE[side]]
+                {
+                    let other = &self.cells[neighbor];
+                    return Err(format!(
+                        "wall between ({}, {}) and ({}, {}) does not match",
+                        cell.x, cell.y, other.x, other.y
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Topology for DeltaBoard {
+    fn cell_count(&self) -> usize {
+        self.cells.len()
+    }
+
+    fn adjacent(&self, cell: usize) -> Vec<usize> {
+        self.neighbors(cell).into_iter().flatten().collect()
+    }
+
+    fn passages(&self, cell: usize) -> Vec<usize> {
+        let walls = &self.cells[cell].walls;
+        self.neighbors(cell)
+            .into_iter()
+            .zip(walls)
+            .filter_map(|(neighbor, wall)| neighbor.filter(|_| !wall))
+            .collect()
+    }
+
+    fn remove_wall(&mut self, cell: usize, neighbor: usize) {
+        let side = self.side(cell, neighbor);
+        self.cells[cell].walls[side] = false;
+        self.cells[neighbor].walls[OPPOSITE[side]] = false;
+        self.cells[cell].visited = true;
+        self.cells[neighbor].visited = true;
+    }
+
+    fn visited(&self, cell: usize) -> bool {
+        self.cells[cell].visited
+    }
+
+    fn set_visited(&mut self, cell: usize) {
+        self.cells[cell].visited = true;
+    }
+
+    fn start(&self) -> usize {
+        self.start
+    }
+
+    fn goal(&self) -> usize {
+        self.goal
+    }
+
+    fn center(&self, cell: usize) -> Vector2 {
+        // the centroid of the corners
+        let [a, b, c] = self.corners(cell);
+        Vector2::new((a.x + b.x + c.x) / 3.0, (a.y + b.y + c.y) / 3.0)
+    }
+
+    fn cell_size(&self) -> f32 {
+        self.cell_size as f32
+    }
+
+    fn bounds(&self) -> Rectangle {
+        Rectangle::new(
+            self.x as f32,
+            self.y as f32,
+            (self.width + 1) as f32 * self.cell_size as f32 / 2.0,
+            self.height as f32 * self.row_height(),
+        )
+    }
+
+    fn draw_board(&self, style: &Style, renderer: &mut dyn Renderer) {
+        for (index, cell) in self.cells.iter().enumerate() {
+            let [tip, left, right] = self.corners(index);
+            if !cell.visited && style.unvisited_color.a > 0 {
+                // raylib only fills counter clockwise triangles
+                if cell.points_up() {
+                    renderer.triangle(tip, left, right, style.unvisited_color);
+                } else {
+                    renderer.triangle(tip, right, left, style.unvisited_color);
+                }
+            }
+            // shared walls are drawn by the cell with the lower index
+            let sides = [(left, tip), (tip, right), (left, right)];
+            for (side, neighbor) in self.neighbors(index).into_iter().enumerate() {
+                if cell.walls[side] && neighbor.is_none_or(|neighbor| neighbor > index) {
+                    let (from, to) = sides[side];
+                    renderer.line(from, to, style.wall_width, style.wall_color);
+                }
+            }
+        }
+        for (index, color) in [
+            (self.start, style.start_color),
+            (self.goal, style.goal_color),
+        ] {
+            renderer.circle(self.center(index), self.cell_size as f32 / 6.0, color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::{analysis, generator, headless, solver::bfs::Bfs};
+
+    #[test]
+    fn up_and_down_neighbors() {
+        let board = DeltaBoard::new(0, 5, 3, 20);
+        let at = |x, y| Some(board.get_index(x, y));
+        // pointing up, the base is shared with the cell below
+        assert!(board.cells[board.get_index(2, 0)].points_up());
+        assert_eq!(
+            board.neighbors(board.get_index(2, 0)),
+            [at(1, 0), at(3, 0), at(2, 1)]
+        );
+        // pointing down, the base is shared with the cell above
+        assert!(!board.cells[board.get_index(2, 1)].points_up());
+        assert_eq!(
+            board.neighbors(board.get_index(2, 1)),
+            [at(1, 1), at(3, 1), at(2, 0)]
+        );
+        // the edges of the board
+        assert_eq!(
+            board.neighbors(board.get_index(1, 0)),
+            [at(0, 0), at(2, 0), None]
+        );
+        assert_eq!(
+            board.neighbors(board.get_index(4, 2)),
+            [at(3, 2), None, None]
+        );
+
+        for index in 0..board.cells.len() {
+            for (side, neighbor) in board.neighbors(index).into_iter().enumerate() {
+                if let Some(neighbor) = neighbor {
+                    assert_eq!(board.neighbors(neighbor)[OPPOSITE[side]], Some(index));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn removing_a_base() {
+        let mut board = DeltaBoard::new(0, 4, 2, 20);
+        let (up, down) = (board.get_index(0, 0), board.get_index(0, 1));
+        board.remove_wall(down, up);
+        assert!(!board.cells[up].walls[2] && !board.cells[down].walls[2]);
+        assert_eq!(board.passages(up), [down]);
+        assert_eq!(board.check_walls(), Ok(()));
+    }
+
+    #[test]
+    fn generators_make_perfect_mazes() {
+        for name in generator::ALGORITHMS {
+            let mut board = DeltaBoard::new(0, 9, 6, 20);
+            let Some(mut generator) = generator::for_topology(name, &mut board, 4) else {
+                continue;
+            };
+            headless::run(&mut board, generator.as_mut());
+            assert!(analysis::is_perfect(&board), "{name}");
+            assert_eq!(board.check_walls(), Ok(()), "{name}");
+            let mut bfs = Bfs::new(&board);
+            assert!(headless::solve(&board, &mut bfs, 1000).solved, "{name}");
+        }
+    }
+}
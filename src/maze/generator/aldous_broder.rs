@@ -1,23 +1,28 @@
 use rand::prelude::*;
 
-use crate::{
-    maze::{Board, CURSOR_COLOR, Generator, State},
-    raylib,
-};
+use crate::maze::{CURSOR_COLOR, Generator, State, render::Renderer, topology::Topology};
 
 pub struct AldousBroder {
     visited: Vec<usize>,
     current_cell: usize,
-    rng: ThreadRng,
+    /// number of cells in the maze
+    total: usize,
+    rng: StdRng,
 }
 
 impl AldousBroder {
-    pub fn new(board: &Board) -> Self {
-        let mut rng = rand::rng();
-        let current_cell = rng.random_range(0..board.board_size ^ 2) as usize;
+    pub fn new<T: Topology>(board: &T) -> Self {
+        Self::with_seed(board, rand::random())
+    }
+
+    pub fn with_seed<T: Topology>(board: &T, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let cells = board.enabled_cells();
+        let current_cell = cells[rng.random_range(0..cells.len())];
         Self {
             visited: vec![current_cell],
             current_cell,
+            total: cells.len(),
             rng,
         }
     }
@@ -27,14 +32,13 @@ impl AldousBroder {
     }
 }
 
-impl Generator for AldousBroder {
-    fn step(&mut self, board: &mut Board) -> State {
+impl<T: Topology> Generator<T> for AldousBroder {
+    fn step(&mut self, board: &mut T) -> State {
+        if self.visited.len() >= self.total {
+            return State::GenerationDone;
+        }
         // get the neighbors of the current cell and pick a random neighbor
-        let neighbors: Vec<usize> = board
-            .neighbors(self.current_cell)
-            .into_iter()
-            .flatten()
-            .collect();
+        let neighbors = board.adjacent(self.current_cell);
         let index = self.rng.random_range(0..neighbors.len());
         let next = neighbors[index];
         // remove wall
@@ -44,23 +48,18 @@ impl Generator for AldousBroder {
         }
         self.current_cell = next;
 
-        if self.visited.len() >= board.cells.len() {
+        if self.visited.len() >= self.total {
             State::GenerationDone
         } else {
             State::Generate
         }
     }
 
-    fn draw(&self, board: &Board) {
-        unsafe {
-            raylib::DrawCircle(
-                (board.x + board.cells[self.current_cell].x * board.cell_size + board.cell_size / 2)
-                    as i32,
-                (board.y + board.cells[self.current_cell].y * board.cell_size + board.cell_size / 2)
-                    as i32,
-                board.cell_size as f32 / 4.0,
-                CURSOR_COLOR,
-            );
-        }
+    fn draw(&self, board: &T, renderer: &mut dyn Renderer) {
+        renderer.circle(
+            board.center(self.current_cell),
+            board.cell_size() / 4.0,
+            CURSOR_COLOR,
+        );
     }
 }
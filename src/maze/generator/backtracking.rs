@@ -1,39 +1,55 @@
 use rand::prelude::*;
 
-use crate::maze::{Board, CURSOR_COLOR, Generator, State};
+use crate::maze::{CURSOR_COLOR, Generator, State, render::Renderer, topology::Topology};
 
-#[derive(Default)]
 pub struct Backtracking {
-    current: usize,
-    rng: ThreadRng,
+    /// starts at the entrance on the first step
+    current: Option<usize>,
+    stack: Vec<usize>,
+    rng: StdRng,
 }
 
 impl Backtracking {
     pub fn new() -> Self {
+        Self::with_seed(rand::random())
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
         Self {
-            current: 0,
-            rng: rand::rng(),
+            current: None,
+            stack: vec![],
+            rng: StdRng::seed_from_u64(seed),
         }
     }
 }
 
-impl Generator for Backtracking {
-    fn step(&mut self, board: &mut Board) -> State {
-        let n = board.neighbors(self.current);
-        let free: Option<&Option<usize>> = n
-            .iter()
-            .filter(|i| i.is_some() && !board.cells[i.unwrap()].visited)
+impl Default for Backtracking {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Topology> Generator<T> for Backtracking {
+    fn step(&mut self, board: &mut T) -> State {
+        let current = *self.current.get_or_insert_with(|| {
+            self.stack.push(board.start());
+            board.start()
+        });
+        let free = board
+            .adjacent(current)
+            .into_iter()
+            .filter(|&i| !board.visited(i))
             .choose(&mut self.rng);
 
-        if let Some(&Some(free)) = free {
+        if let Some(free) = free {
             // remove the walls
-            board.remove_wall(self.current, free);
+            board.remove_wall(current, free);
             // set next cell as current
-            board.cells[free].visited = true;
-            self.current = free;
-            board.path.push(free)
-        } else if let Some(last) = board.path.pop() {
-            self.current = last;
+            board.set_visited(free);
+            self.current = Some(free);
+            self.stack.push(free)
+        } else if let Some(last) = self.stack.pop() {
+            self.current = Some(last);
         } else {
             return State::GenerationDone;
         }
@@ -41,16 +57,12 @@ impl Generator for Backtracking {
         State::Generate
     }
 
-    fn draw(&self, board: &Board) {
+    fn draw(&self, board: &T, renderer: &mut dyn Renderer) {
         // draw the result
-        use crate::raylib;
-        unsafe {
-            raylib::DrawCircle(
-                (board.x + board.cells[self.current].x * board.cell_size + board.cell_size / 2)
-                    as i32,
-                (board.y + board.cells[self.current].y * board.cell_size + board.cell_size / 2)
-                    as i32,
-                board.cell_size as f32 / 10.0,
+        if let Some(current) = self.current {
+            renderer.circle(
+                board.center(current),
+                board.cell_size() / 10.0,
                 CURSOR_COLOR,
             );
         }
@@ -1,50 +1,65 @@
 use rand::prelude::*;
 
-use crate::maze::{Board, Generator, State};
+use crate::maze::{Board, Generator, State, render::Renderer};
 
 pub const BOOL_TRUE_PROBABILITY: f64 = 0.5;
 
-#[derive(Default)]
 pub struct BinaryTree {
     x: usize,
     y: usize,
-    rng: ThreadRng,
+    rng: StdRng,
 }
 
 impl BinaryTree {
     pub fn new() -> Self {
+        Self::with_seed(rand::random())
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
         Self {
             x: 0,
             y: 0,
-            rng: rand::rng(),
+            rng: StdRng::seed_from_u64(seed),
         }
     }
 }
 
+impl Default for BinaryTree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Generator for BinaryTree {
     fn step(&mut self, board: &mut Board) -> State {
-        if self.x >= board.board_size - 1 && self.y >= board.board_size - 1 {
+        if self.x >= board.width - 1 && self.y >= board.height - 1 {
+            super::connect(board, &mut self.rng);
             return State::GenerationDone;
         }
 
-        let east = if self.x == board.board_size - 1 {
-            false
-        } else if self.y == board.board_size - 1 {
-            true
-        } else {
-            self.rng.random_bool(BOOL_TRUE_PROBABILITY)
-        };
-
+        // carve east or south, masked cells are left out
         let cell = board.get_index(self.x, self.y);
-        let neighbor = if east {
-            board.get_index(self.x + 1, self.y)
-        } else {
-            board.get_index(self.x, self.y + 1)
+        let east = (self.x < board.width - 1).then(|| board.get_index(self.x + 1, self.y));
+        let south = (self.y < board.height - 1).then(|| board.get_index(self.x, self.y + 1));
+        let [east, south] = [east, south].map(|neighbor| {
+            neighbor.filter(|&neighbor| !board.cells[cell].masked && !board.cells[neighbor].masked)
+        });
+        let neighbor = match (east, south) {
+            (Some(east), Some(south)) => {
+                if self.rng.random_bool(BOOL_TRUE_PROBABILITY) {
+                    Some(east)
+                } else {
+                    Some(south)
+                }
+            }
+            (east, south) => east.or(south),
         };
 
-        board.remove_wall(cell, neighbor);
+        if let Some(neighbor) = neighbor {
+            board.remove_wall(cell, neighbor);
+        }
 
-        if self.x == board.board_size - 1 {
+        if self.x == board.width - 1 {
             self.x = 0;
             self.y += 1;
         } else {
@@ -53,5 +68,5 @@ impl Generator for BinaryTree {
         State::Generate
     }
 
-    fn draw(&self, _board: &Board) {}
+    fn draw(&self, _board: &Board, _renderer: &mut dyn Renderer) {}
 }
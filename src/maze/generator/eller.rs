@@ -1,10 +1,10 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 use disjoint::DisjointSet;
 use rand::prelude::*;
 
 use crate::{
-    maze::{Board, CURSOR_COLOR, Generator, State},
+    maze::{Board, CURSOR_COLOR, Generator, State, render::Renderer},
     raylib,
 };
 pub const BOOL_TRUE_PROBABILITY: f64 = 0.5;
@@ -21,52 +21,71 @@ pub struct Eller {
     y: usize,
     merged: DisjointSet,
     state: IState,
-    row: HashMap<usize, Vec<usize>>,
-    rng: ThreadRng,
+    row: BTreeMap<usize, Vec<usize>>,
+    rng: StdRng,
 }
 
 impl Eller {
     pub fn new(board: &Board) -> Self {
+        Self::with_seed(board, rand::random())
+    }
+
+    pub fn with_seed(board: &Board, seed: u64) -> Self {
         Self {
             x: 0,
             y: 0,
             merged: DisjointSet::with_len(board.cells.len()),
             state: IState::Merge,
-            row: HashMap::new(),
-            rng: rand::rng(),
+            row: BTreeMap::new(),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Whether the wall between two cells may be removed, masked cells stay
+    /// closed.
+    fn open(board: &Board, cell: usize, neighbor: usize) -> bool {
+        !board.cells[cell].masked && !board.cells[neighbor].masked
+    }
+
+    /// Add a cell to the sets of the current row, masked cells belong to
+    /// none.
+    fn add_to_row(&mut self, board: &Board, cell: usize) {
+        if !board.cells[cell].masked {
+            self.row
+                .entry(self.merged.root_of(cell))
+                .or_default()
+                .push(cell);
         }
     }
 }
 
 impl Generator for Eller {
     fn step(&mut self, board: &mut Board) -> State {
+        if board.width == 1 || board.height == 1 {
+            return super::corridor(board);
+        }
         match self.state {
             IState::Merge => {
                 let cell = board.get_index(self.x, self.y);
                 let neighbor = board.get_index(self.x + 1, self.y);
 
-                if !self.merged.is_joined(cell, neighbor)
+                if Self::open(board, cell, neighbor)
+                    && !self.merged.is_joined(cell, neighbor)
                     && (self.rng.random_bool(BOOL_TRUE_PROBABILITY))
                 {
                     self.merged.join(cell, neighbor);
                     board.remove_wall(cell, neighbor);
                 }
 
-                self.row
-                    .entry(self.merged.root_of(cell))
-                    .or_default()
-                    .push(cell);
+                self.add_to_row(board, cell);
 
                 self.x += 1;
 
                 // end of the row
-                if self.x >= board.board_size - 1 {
-                    self.row
-                        .entry(self.merged.root_of(board.get_index(self.x, self.y)))
-                        .or_default()
-                        .push(board.get_index(self.x, self.y));
+                if self.x >= board.width - 1 {
+                    self.add_to_row(board, board.get_index(self.x, self.y));
                     self.x = 0;
-                    if self.y == board.board_size - 1 {
+                    if self.y == board.height - 1 {
                         self.state = IState::Last;
                     } else {
                         self.state = IState::Bottom;
@@ -77,7 +96,8 @@ impl Generator for Eller {
             IState::Bottom => {
                 let cell = board.get_index(self.x, self.y);
                 let neighbor = board.get_index(self.x, self.y + 1);
-                if !self.merged.is_joined(cell, neighbor)
+                if Self::open(board, cell, neighbor)
+                    && !self.merged.is_joined(cell, neighbor)
                     && self.rng.random_bool(BOOL_TRUE_PROBABILITY)
                 {
                     self.merged.join(cell, neighbor);
@@ -86,21 +106,27 @@ impl Generator for Eller {
                 }
 
                 self.x += 1;
-                if self.x >= board.board_size {
+                if self.x >= board.width {
+                    // every set goes on in the next row, unless all its
+                    // cells are above masked cells
                     for cells in self.row.values() {
-                        if let Some(&index) = cells.choose(&mut self.rng) {
-                            let neighbor =
-                                board.get_index(board.cells[index].x, board.cells[index].y + 1);
+                        let below: Vec<(usize, usize)> = cells
+                            .iter()
+                            .map(|&index| {
+                                let cell = &board.cells[index];
+                                (index, board.get_index(cell.x, cell.y + 1))
+                            })
+                            .filter(|&(_, neighbor)| !board.cells[neighbor].masked)
+                            .collect();
+                        if let Some(&(index, neighbor)) = below.choose(&mut self.rng) {
                             board.remove_wall(index, neighbor);
                             self.merged.join(index, neighbor);
-                        } else {
-                            panic!("no top neighbor");
                         }
                     }
                     self.row.clear();
                     self.x = 0;
                     self.y += 1;
-                    if self.y == board.board_size - 1 {
+                    if self.y == board.height - 1 {
                         self.state = IState::LastMerge;
                     } else {
                         self.state = IState::Merge;
@@ -112,26 +138,20 @@ impl Generator for Eller {
                 let cell = board.get_index(self.x, self.y);
                 let neighbor = board.get_index(self.x + 1, self.y);
 
-                if !self.merged.is_joined(cell, neighbor) {
+                if Self::open(board, cell, neighbor) && !self.merged.is_joined(cell, neighbor) {
                     self.merged.join(cell, neighbor);
                     board.remove_wall(cell, neighbor);
                 }
 
-                self.row
-                    .entry(self.merged.root_of(cell))
-                    .or_default()
-                    .push(cell);
+                self.add_to_row(board, cell);
 
                 self.x += 1;
 
                 // end of the row
-                if self.x >= board.board_size - 1 {
-                    self.row
-                        .entry(self.merged.root_of(board.get_index(self.x, self.y)))
-                        .or_default()
-                        .push(board.get_index(self.x, self.y));
+                if self.x >= board.width - 1 {
+                    self.add_to_row(board, board.get_index(self.x, self.y));
                     self.x = 0;
-                    if self.y == board.board_size - 1 {
+                    if self.y == board.height - 1 {
                         self.state = IState::Last;
                     } else {
                         self.state = IState::Bottom;
@@ -142,38 +162,43 @@ impl Generator for Eller {
             IState::Last => {
                 let cell = board.get_index(self.x, self.y);
                 let neighbor = board.get_index(self.x, self.y - 1);
-                if !self.merged.is_joined(cell, neighbor) {
+                if Self::open(board, cell, neighbor) && !self.merged.is_joined(cell, neighbor) {
                     self.merged.join(cell, neighbor);
                     board.remove_wall(cell, neighbor);
                     self.row.remove(&self.merged.root_of(cell));
                 }
 
                 self.x += 1;
-                if self.x >= board.board_size - 1 {
+                if self.x >= board.width - 1 {
                     for cells in self.row.values() {
-                        if let Some(&index) = cells.choose(&mut self.rng) {
-                            let neighbor =
-                                board.get_index(board.cells[index].x, board.cells[index].y - 1);
+                        let above: Vec<(usize, usize)> = cells
+                            .iter()
+                            .map(|&index| {
+                                let cell = &board.cells[index];
+                                (index, board.get_index(cell.x, cell.y - 1))
+                            })
+                            .filter(|&(_, neighbor)| !board.cells[neighbor].masked)
+                            .collect();
+                        if let Some(&(index, neighbor)) = above.choose(&mut self.rng) {
                             board.remove_wall(index, neighbor);
-                        } else {
-                            panic!("no top neigbhor");
                         }
                     }
                     self.row.clear();
                 }
+                super::connect(board, &mut self.rng);
                 State::GenerationDone
             }
         }
     }
 
-    fn draw(&self, board: &Board) {
-        unsafe {
-            raylib::DrawCircle(
-                (board.x + self.x * board.cell_size + board.cell_size / 2) as i32,
-                (board.y + self.y * board.cell_size + board.cell_size / 2) as i32,
-                board.cell_size as f32 / 5.0,
-                CURSOR_COLOR,
-            );
-        }
+    fn draw(&self, board: &Board, renderer: &mut dyn Renderer) {
+        renderer.circle(
+            raylib::Vector2::new(
+                (board.x + self.x * board.cell_size + board.cell_size / 2) as f32,
+                (board.y + self.y * board.cell_size + board.cell_size / 2) as f32,
+            ),
+            board.cell_size as f32 / 5.0,
+            CURSOR_COLOR,
+        );
     }
 }
@@ -1,20 +1,29 @@
 use rand::prelude::*;
 
-use crate::maze::{Board, Generator, State};
+use crate::maze::{Generator, State, render::Renderer, topology::Topology};
 
 pub struct GrowingTree {
     visited: Vec<usize>,
     cells: Vec<usize>,
-    rng: ThreadRng,
+    /// number of cells in the maze
+    total: usize,
+    rng: StdRng,
 }
 
 impl GrowingTree {
-    pub fn new(board: &Board) -> Self {
-        let cell = rand::rng().random_range(0..board.board_size ^ 2) as usize;
+    pub fn new<T: Topology>(board: &T) -> Self {
+        Self::with_seed(board, rand::random())
+    }
+
+    pub fn with_seed<T: Topology>(board: &T, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let enabled = board.enabled_cells();
+        let cell = enabled[rng.random_range(0..enabled.len())];
         Self {
             visited: vec![],
             cells: vec![cell],
-            rng: rand::rng(),
+            total: enabled.len(),
+            rng,
         }
     }
 
@@ -23,14 +32,13 @@ impl GrowingTree {
     }
 }
 
-impl Generator for GrowingTree {
-    fn step(&mut self, board: &mut Board) -> State {
+impl<T: Topology> Generator<T> for GrowingTree {
+    fn step(&mut self, board: &mut T) -> State {
         let index = self.rng.random_range(0..self.cells.len());
         let cell = self.cells[index];
         let neighbors: Vec<usize> = board
-            .neighbors(cell)
+            .adjacent(cell)
             .into_iter()
-            .flatten()
             .filter(|item| !self.contains(item))
             .collect();
 
@@ -44,12 +52,12 @@ impl Generator for GrowingTree {
             self.cells.push(neighbor);
         }
 
-        if self.visited.len() >= board.cells.len() {
+        if self.visited.len() >= self.total {
             State::GenerationDone
         } else {
             State::Generate
         }
     }
 
-    fn draw(&self, _board: &Board) {}
+    fn draw(&self, _board: &T, _renderer: &mut dyn Renderer) {}
 }
@@ -1,9 +1,6 @@
 use rand::prelude::*;
 
-use crate::{
-    maze::{Board, CURSOR_COLOR, Generator, State},
-    raylib,
-};
+use crate::maze::{CURSOR_COLOR, Generator, State, render::Renderer, topology::Topology};
 
 enum IState {
     Hunt,
@@ -14,18 +11,26 @@ pub struct HuntAndKill {
     visited: Vec<usize>,
     current_cell: usize,
     state: IState,
-    rng: ThreadRng,
+    /// number of cells in the maze
+    total: usize,
+    rng: StdRng,
 }
 
 impl HuntAndKill {
-    pub fn new(board: &mut Board) -> Self {
-        let mut rng = rand::rng();
-        let current_cell = rng.random_range(0..board.board_size ^ 2) as usize;
-        board.cells[current_cell].visited = true;
+    pub fn new<T: Topology>(board: &mut T) -> Self {
+        Self::with_seed(board, rand::random())
+    }
+
+    pub fn with_seed<T: Topology>(board: &mut T, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let cells = board.enabled_cells();
+        let current_cell = cells[rng.random_range(0..cells.len())];
+        board.set_visited(current_cell);
         Self {
             visited: vec![current_cell],
             current_cell,
             state: IState::Kill,
+            total: cells.len(),
             rng,
         }
     }
@@ -35,52 +40,31 @@ impl HuntAndKill {
     }
 }
 
-impl Generator for HuntAndKill {
-    fn step(&mut self, board: &mut Board) -> State {
+impl<T: Topology> Generator<T> for HuntAndKill {
+    fn step(&mut self, board: &mut T) -> State {
         match self.state {
             IState::Hunt => {
-                for y in 0..board.board_size {
-                    for x in 0..board.board_size {
-                        let current = board.get_index(x, y);
-                        // skip if visited
-                        if self.contains(&(current)) {
-                            continue;
-                        }
-                        // get visited
-                        let visited_neighbors: Vec<usize> = board
-                            .neighbors(current)
-                            .into_iter()
-                            .flatten()
-                            .filter(|item| self.contains(item))
-                            .collect();
+                // scan the cells in index order for an unvisited cell next to
+                // the maze
+                for current in 0..board.cell_count() {
+                    // skip if visited
+                    if self.contains(&current) {
+                        continue;
+                    }
+                    // get visited
+                    let visited_neighbors: Vec<usize> = board
+                        .adjacent(current)
+                        .into_iter()
+                        .filter(|item| self.contains(item))
+                        .collect();
 
-                        if !visited_neighbors.is_empty() {
-                            self.current_cell = current;
-                            board.cells[current].visited = true;
-                            self.visited.push(current);
-                            let index = self.rng.random_range(0..visited_neighbors.len());
-                            let next = visited_neighbors[index];
-                            match board.cells[self.current_cell].direction(&board.cells[next]) {
-                                crate::maze::Direction::North => {
-                                    board.cells[self.current_cell].walls.top = false;
-                                    board.cells[next].walls.bottom = false;
-                                }
-                                crate::maze::Direction::South => {
-                                    board.cells[self.current_cell].walls.bottom = false;
-                                    board.cells[next].walls.top = false;
-                                }
-                                crate::maze::Direction::East => {
-                                    board.cells[self.current_cell].walls.right = false;
-                                    board.cells[next].walls.left = false;
-                                }
-                                crate::maze::Direction::West => {
-                                    board.cells[self.current_cell].walls.left = false;
-                                    board.cells[next].walls.right = false;
-                                }
-                            }
-                            self.state = IState::Kill;
-                            return State::Generate;
-                        }
+                    if !visited_neighbors.is_empty() {
+                        self.current_cell = current;
+                        self.visited.push(current);
+                        let index = self.rng.random_range(0..visited_neighbors.len());
+                        board.remove_wall(current, visited_neighbors[index]);
+                        self.state = IState::Kill;
+                        return State::Generate;
                     }
                 }
                 return State::GenerationDone;
@@ -88,9 +72,8 @@ impl Generator for HuntAndKill {
             IState::Kill => {
                 // get the neighbors of the current cell and pick a random neighbor
                 let neighbors: Vec<usize> = board
-                    .neighbors(self.current_cell)
+                    .adjacent(self.current_cell)
                     .into_iter()
-                    .flatten()
                     .filter(|item| !self.contains(item))
                     .collect();
 
@@ -104,48 +87,25 @@ impl Generator for HuntAndKill {
                 let next = neighbors[index];
                 // remove wall
                 if !self.contains(&next) {
-                    match board.cells[self.current_cell].direction(&board.cells[next]) {
-                        crate::maze::Direction::North => {
-                            board.cells[self.current_cell].walls.top = false;
-                            board.cells[next].walls.bottom = false;
-                        }
-                        crate::maze::Direction::South => {
-                            board.cells[self.current_cell].walls.bottom = false;
-                            board.cells[next].walls.top = false;
-                        }
-                        crate::maze::Direction::East => {
-                            board.cells[self.current_cell].walls.right = false;
-                            board.cells[next].walls.left = false;
-                        }
-                        crate::maze::Direction::West => {
-                            board.cells[self.current_cell].walls.left = false;
-                            board.cells[next].walls.right = false;
-                        }
-                    }
-                    board.cells[next].visited = true;
+                    board.remove_wall(self.current_cell, next);
                     self.visited.push(next);
                 }
                 self.current_cell = next;
             }
         }
 
-        if self.visited.len() >= board.cells.len() {
+        if self.visited.len() >= self.total {
             State::GenerationDone
         } else {
             State::Generate
         }
     }
 
-    fn draw(&self, board: &Board) {
-        unsafe {
-            raylib::DrawCircle(
-                (board.x + board.cells[self.current_cell].x * board.cell_size + board.cell_size / 2)
-                    as i32,
-                (board.y + board.cells[self.current_cell].y * board.cell_size + board.cell_size / 2)
-                    as i32,
-                board.cell_size as f32 / 4.0,
-                CURSOR_COLOR,
-            );
-        }
+    fn draw(&self, board: &T, renderer: &mut dyn Renderer) {
+        renderer.circle(
+            board.center(self.current_cell),
+            board.cell_size() / 4.0,
+            CURSOR_COLOR,
+        );
     }
 }
@@ -1,49 +1,27 @@
 use disjoint::DisjointSet;
 use rand::prelude::*;
 
-use crate::maze::{Board, Generator, State};
-
-#[derive(Debug, Eq, PartialEq)]
-enum Direction {
-    North,
-    West,
-}
-
-#[derive(Debug)]
-struct Edge {
-    x: usize,
-    y: usize,
-    direction: Direction,
-}
+use crate::maze::{Generator, State, render::Renderer, topology::Topology};
 
 pub struct Kruskal {
-    edges: Vec<Edge>,
-    cells: Vec<(usize, usize, usize)>,
+    /// pairs of adjacent cells, in random order
+    edges: Vec<(usize, usize)>,
     merged: DisjointSet,
-    visited_edges: Vec<Edge>,
-    step: usize,
 }
 
 impl Kruskal {
-    pub fn new(board: &Board) -> Self {
-        let mut rng = rand::rng();
-        // pupulate the edges
-        let mut edges: Vec<Edge> = vec![];
-        for y in 0..board.board_size {
-            for x in 0..board.board_size {
-                if y > 0 {
-                    edges.push(Edge {
-                        x,
-                        y,
-                        direction: Direction::North,
-                    })
-                }
-                if x > 0 {
-                    edges.push(Edge {
-                        x,
-                        y,
-                        direction: Direction::West,
-                    })
+    pub fn new<T: Topology>(board: &T) -> Self {
+        Self::with_seed(board, rand::random())
+    }
+
+    pub fn with_seed<T: Topology>(board: &T, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        // pupulate the edges, every pair once
+        let mut edges: Vec<(usize, usize)> = vec![];
+        for cell in 0..board.cell_count() {
+            for neighbor in board.adjacent(cell) {
+                if neighbor < cell {
+                    edges.push((cell, neighbor));
                 }
             }
         }
@@ -51,51 +29,22 @@ impl Kruskal {
 
         Self {
             edges,
-            cells: Vec::new(),
-            merged: DisjointSet::with_len(board.cells.len()),
-            visited_edges: Vec::new(),
-            step: 1,
+            merged: DisjointSet::with_len(board.cell_count()),
         }
     }
 }
 
-impl Generator for Kruskal {
-    fn step(&mut self, board: &mut Board) -> State {
-        let edge: Option<Edge> = self.edges.pop();
-        if let Some(edge) = edge {
-            let index_cell = board.get_index(edge.x, edge.y);
-            let index_neighbor = if edge.direction == Direction::North {
-                board.get_index(edge.x, edge.y - 1)
-            } else {
-                board.get_index(edge.x - 1, edge.y)
-            };
-
-            if !self.merged.is_joined(index_cell, index_neighbor) {
-                self.merged.join(index_cell, index_neighbor);
-                self.cells.push((self.step, index_cell, index_neighbor));
-
-                //remove walls
-                match edge.direction {
-                    Direction::North => {
-                        board.cells[index_cell].walls.top = false;
-                        board.cells[index_neighbor].walls.bottom = false;
-                    }
-                    Direction::West => {
-                        board.cells[index_cell].walls.left = false;
-                        board.cells[index_neighbor].walls.right = false;
-                    }
-                }
-                board.cells[index_cell].visited = true;
-                board.cells[index_neighbor].visited = true;
-            }
-            self.visited_edges.push(edge);
-        } else {
+impl<T: Topology> Generator<T> for Kruskal {
+    fn step(&mut self, board: &mut T) -> State {
+        let Some((cell, neighbor)) = self.edges.pop() else {
             return State::GenerationDone;
+        };
+        if !self.merged.is_joined(cell, neighbor) {
+            self.merged.join(cell, neighbor);
+            board.remove_wall(cell, neighbor);
         }
-
-        self.step += 1;
         State::Generate
     }
 
-    fn draw(&self, _board: &Board) {}
+    fn draw(&self, _board: &T, _renderer: &mut dyn Renderer) {}
 }
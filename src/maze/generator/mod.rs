@@ -9,3 +9,144 @@ pub mod prim;
 pub mod recursive_division;
 pub mod sidewinder;
 pub mod wilson;
+
+use disjoint::DisjointSet;
+use rand::prelude::*;
+
+use crate::maze::{Board, Generator, State, topology::Topology};
+
+/// Names of the generators, in the order the gui lists them.
+pub const ALGORITHMS: [&str; 11] = [
+    "recursive backtracker",
+    "kruskal",
+    "eller",
+    "prim",
+    "recursive division",
+    "aldous broder",
+    "wilson",
+    "hunt and kill",
+    "growing tree",
+    "binary tree",
+    "sidewinder",
+];
+
+/// Create the generator called `name` (one of `ALGORITHMS`) for `board`.
+pub fn by_name(name: &str, board: &mut Board, seed: u64) -> Option<Box<dyn Generator>> {
+    let generator: Box<dyn Generator> = match name {
+        "recursive backtracker" => Box::new(backtracking::Backtracking::with_seed(seed)),
+        "kruskal" => Box::new(kruskal::Kruskal::with_seed(board, seed)),
+        "eller" => Box::new(eller::Eller::with_seed(board, seed)),
+        "prim" => Box::new(prim::Prim::with_seed(board, seed)),
+        "recursive division" => Box::new(recursive_division::RecursiveDivision::with_seed(
+            board, seed,
+        )),
+        "aldous broder" => Box::new(aldous_broder::AldousBroder::with_seed(board, seed)),
+        "wilson" => Box::new(wilson::Wilson::with_seed(board, seed)),
+        "hunt and kill" => Box::new(hunt_and_kill::HuntAndKill::with_seed(board, seed)),
+        "growing tree" => Box::new(growing_tree::GrowingTree::with_seed(board, seed)),
+        "binary tree" => Box::new(binary_tree::BinaryTree::with_seed(seed)),
+        "sidewinder" => Box::new(sidewinder::Sidewinder::with_seed(board, seed)),
+        _ => return None,
+    };
+    Some(generator)
+}
+
+/// Create the generator called `name` for any grid, `None` for the
+/// generators that only work on the square `Board`.
+pub fn for_topology<T: Topology>(
+    name: &str,
+    board: &mut T,
+    seed: u64,
+) -> Option<Box<dyn Generator<T>>> {
+    let generator: Box<dyn Generator<T>> = match name {
+        "recursive backtracker" => Box::new(backtracking::Backtracking::with_seed(seed)),
+        "kruskal" => Box::new(kruskal::Kruskal::with_seed(board, seed)),
+        "prim" => Box::new(prim::Prim::with_seed(board, seed)),
+        "aldous broder" => Box::new(aldous_broder::AldousBroder::with_seed(board, seed)),
+        "wilson" => Box::new(wilson::Wilson::with_seed(board, seed)),
+        "hunt and kill" => Box::new(hunt_and_kill::HuntAndKill::with_seed(board, seed)),
+        "growing tree" => Box::new(growing_tree::GrowingTree::with_seed(board, seed)),
+        _ => return None,
+    };
+    Some(generator)
+}
+
+/**
+ * Carve the only maze a board one cell wide or high has, a straight
+ * corridor. The generators that work on rows and columns need at least two
+ * of each and use this instead.
+ */
+pub(crate) fn corridor(board: &mut Board) -> State {
+    for index in 0..board.cells.len() {
+        board.cells[index].visited = true;
+        if index > 0 && !board.cells[index - 1].masked && !board.cells[index].masked {
+            board.remove_wall(index - 1, index);
+        }
+    }
+    State::GenerationDone
+}
+
+/**
+ * Join the parts of a maze that a generator left apart, by removing random
+ * walls between cells that are not connected yet. Generators that walk the
+ * grid row by row cannot cross masked cells, this turns the forest they
+ * leave behind into a single maze. A maze without masked cells is left as it
+ * is.
+ */
+pub(crate) fn connect(board: &mut Board, rng: &mut StdRng) {
+    let mut sets = DisjointSet::with_len(board.cells.len());
+    let mut walls = Vec::new();
+    for cell in 0..board.cells.len() {
+        for neighbor in board.passages(cell) {
+            sets.join(cell, neighbor);
+        }
+        for neighbor in board.neighbors(cell).into_iter().flatten() {
+            if neighbor > cell {
+                walls.push((cell, neighbor));
+            }
+        }
+    }
+    walls.shuffle(rng);
+    for (cell, neighbor) in walls {
+        if !sets.is_joined(cell, neighbor) {
+            sets.join(cell, neighbor);
+            board.remove_wall(cell, neighbor);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::{analysis, headless};
+
+    fn generate(name: &str, seed: u64) -> Board {
+        let mut board = Board::new(0, 12, 9, 10);
+        let mut generator = by_name(name, &mut board, seed).unwrap();
+        headless::run(&mut board, generator.as_mut());
+        board
+    }
+
+    #[test]
+    fn same_seed_same_board() {
+        for name in ALGORITHMS {
+            assert_eq!(generate(name, 7), generate(name, 7), "{name}");
+            assert_ne!(generate(name, 7), generate(name, 8), "{name}");
+        }
+    }
+
+    #[test]
+    fn thin_boards() {
+        for (width, height) in [(1, 1), (1, 5), (5, 1), (2, 1), (1, 2)] {
+            for name in ALGORITHMS {
+                let mut board = Board::new(0, width, height, 10);
+                let mut generator = by_name(name, &mut board, 1).unwrap();
+                headless::run(&mut board, generator.as_mut());
+                assert!(
+                    analysis::is_perfect(&board),
+                    "{name} on {width}x{height} is not perfect"
+                );
+            }
+        }
+    }
+}
@@ -1,9 +1,6 @@
 use rand::prelude::*;
 
-use crate::{
-    maze::{Board, CURSOR_COLOR, Generator, State},
-    raylib,
-};
+use crate::maze::{CURSOR_COLOR, Generator, State, render::Renderer, topology::Topology};
 
 #[derive(Debug)]
 struct FreeCell {
@@ -14,17 +11,21 @@ struct FreeCell {
 pub struct Prim {
     visited: Vec<usize>,
     cells: Vec<FreeCell>,
-    rng: ThreadRng,
+    rng: StdRng,
 }
 
 impl Prim {
-    pub fn new(board: &Board) -> Self {
-        let mut rng = rand::rng();
-        let current = rng.random_range(0..board.board_size ^ 2) as usize;
+    pub fn new<T: Topology>(board: &T) -> Self {
+        Self::with_seed(board, rand::random())
+    }
+
+    pub fn with_seed<T: Topology>(board: &T, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let enabled = board.enabled_cells();
+        let current = enabled[rng.random_range(0..enabled.len())];
         let cells = board
-            .neighbors(current)
+            .adjacent(current)
             .into_iter()
-            .flatten()
             .map(|index| FreeCell {
                 index,
                 neighbor: current,
@@ -48,8 +49,12 @@ impl Prim {
     }
 }
 
-impl Generator for Prim {
-    fn step(&mut self, board: &mut Board) -> State {
+impl<T: Topology> Generator<T> for Prim {
+    fn step(&mut self, board: &mut T) -> State {
+        // a single cell has nothing to carve
+        if self.cells.is_empty() {
+            return State::GenerationDone;
+        }
         let index = self.rng.random_range(0..self.cells.len());
         let item = self.cells.remove(index);
 
@@ -57,7 +62,7 @@ impl Generator for Prim {
         board.remove_wall(item.index, item.neighbor);
 
         // calc next cells
-        let neighbors: Vec<usize> = board.neighbors(item.index).into_iter().flatten().collect();
+        let neighbors = board.adjacent(item.index);
         for n in &neighbors {
             if !self.contains(n) {
                 self.cells.push(FreeCell {
@@ -76,19 +81,10 @@ impl Generator for Prim {
         }
     }
 
-    fn draw(&self, board: &Board) {
+    fn draw(&self, board: &T, renderer: &mut dyn Renderer) {
         // draw the next cells
         for i in &self.cells {
-            unsafe {
-                raylib::DrawCircle(
-                    (board.x + board.cells[i.index].x * board.cell_size + board.cell_size / 2)
-                        as i32,
-                    (board.y + board.cells[i.index].y * board.cell_size + board.cell_size / 2)
-                        as i32,
-                    board.cell_size as f32 / 5.0,
-                    CURSOR_COLOR,
-                );
-            }
+            renderer.circle(board.center(i.index), board.cell_size() / 5.0, CURSOR_COLOR);
         }
     }
 }
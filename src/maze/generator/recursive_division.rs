@@ -1,7 +1,7 @@
 use rand::prelude::*;
 
 use crate::{
-    maze::{Board, Generator, State},
+    maze::{Board, Generator, State, render::Renderer},
     raylib,
 };
 
@@ -13,25 +13,36 @@ struct Area {
 
 pub struct RecursiveDivision {
     areas: Vec<Area>,
-    rng: ThreadRng,
+    rng: StdRng,
     probability: f64,
     area: Area,
 }
 
 impl RecursiveDivision {
     pub fn new(board: &mut Board) -> Self {
-        //remove all walls
-        for cell in &mut board.cells {
-            if cell.x > 0 {
+        Self::with_seed(board, rand::random())
+    }
+
+    pub fn with_seed(board: &mut Board, seed: u64) -> Self {
+        //remove all walls between cells of the maze
+        for index in 0..board.cells.len() {
+            let [top, bottom, left, right] = board.neighbors(index)[..] else {
+                unreachable!("a cell has four sides")
+            };
+            let cell = &mut board.cells[index];
+            if cell.masked {
+                continue;
+            }
+            if cell.x > 0 && left.is_some() {
                 cell.walls.left = false;
             }
-            if cell.y > 0 {
+            if cell.y > 0 && top.is_some() {
                 cell.walls.top = false;
             }
-            if cell.x < board.board_size - 1 {
+            if cell.x < board.width - 1 && right.is_some() {
                 cell.walls.right = false;
             }
-            if cell.y < board.board_size - 1 {
+            if cell.y < board.height - 1 && bottom.is_some() {
                 cell.walls.bottom = false;
             }
             cell.visited = true;
@@ -39,13 +50,13 @@ impl RecursiveDivision {
         Self {
             areas: vec![Area {
                 start: (0, 0),
-                end: (board.board_size, board.board_size),
+                end: (board.width, board.height),
             }],
-            rng: rand::rng(),
+            rng: StdRng::seed_from_u64(seed),
             probability: 0.5,
             area: Area {
                 start: (0, 0),
-                end: (board.board_size - 1, board.board_size - 1),
+                end: (board.width - 1, board.height - 1),
             },
         }
     }
@@ -62,7 +73,7 @@ impl RecursiveDivision {
             if x != index {
                 let c0 = board.get_index(index, y);
                 board.cells[c0].walls.bottom = true;
-                if y < board.board_size - 1 {
+                if y < board.height - 1 {
                     let c1 = board.get_index(index, y + 1);
                     board.cells[c1].walls.top = true;
                 }
@@ -97,7 +108,7 @@ impl RecursiveDivision {
             if y != index {
                 let c0 = board.get_index(x, index);
                 board.cells[c0].walls.right = true;
-                if x < board.board_size - 1 {
+                if x < board.width - 1 {
                     let c1 = board.get_index(x + 1, index);
                     board.cells[c1].walls.left = true;
                 }
@@ -121,13 +132,18 @@ impl RecursiveDivision {
 
 impl Generator for RecursiveDivision {
     fn step(&mut self, board: &mut Board) -> State {
+        if board.width == 1 || board.height == 1 {
+            return super::corridor(board);
+        }
         let mut new_areas: Vec<Area> = Vec::new();
         if let Some(area) = self.areas.pop() {
             let y = self.rng.random_range(area.start.1..area.end.1 - 1);
             let x = self.rng.random_range(area.start.0..area.end.0 - 1);
-            if (area.start.0 - area.end.0) > (area.start.1 - area.end.1) {
+            let width = area.end.0 - area.start.0;
+            let height = area.end.1 - area.start.1;
+            if width < height {
                 self.split_horizontal(x, y, board, &area, &mut new_areas);
-            } else if (area.start.0 - area.end.0) < (area.start.1 - area.end.1) {
+            } else if width > height {
                 self.split_vertical(x, y, board, &area, &mut new_areas);
             } else if self.rng.random_bool(self.probability) {
                 self.split_horizontal(x, y, board, &area, &mut new_areas);
@@ -138,24 +154,23 @@ impl Generator for RecursiveDivision {
             self.area = area;
             State::Generate
         } else {
+            super::connect(board, &mut self.rng);
             State::GenerationDone
         }
     }
 
-    fn draw(&self, board: &Board) {
-        unsafe {
-            raylib::DrawRectangle(
-                (board.x + self.area.start.0 * board.cell_size) as i32,
-                (board.y + self.area.start.1 * board.cell_size) as i32,
-                ((self.area.end.0 - self.area.start.0) * board.cell_size) as i32,
-                ((self.area.end.1 - self.area.start.1) * board.cell_size) as i32,
-                raylib::Color {
-                    r: 150,
-                    g: 0,
-                    b: 0,
-                    a: 50,
-                },
-            );
-        }
+    fn draw(&self, board: &Board, renderer: &mut dyn Renderer) {
+        renderer.rect(
+            (board.x + self.area.start.0 * board.cell_size) as f32,
+            (board.y + self.area.start.1 * board.cell_size) as f32,
+            ((self.area.end.0 - self.area.start.0) * board.cell_size) as f32,
+            ((self.area.end.1 - self.area.start.1) * board.cell_size) as f32,
+            raylib::Color {
+                r: 150,
+                g: 0,
+                b: 0,
+                a: 50,
+            },
+        );
     }
 }
@@ -1,58 +1,82 @@
 use rand::prelude::*;
 
-use crate::maze::{Board, Generator, State};
+use crate::maze::{Board, Generator, State, render::Renderer};
 
 pub const BOOL_TRUE_PROBABILITY: f64 = 0.5;
 
-#[derive(Default)]
 pub struct Sidewinder {
     x: usize,
     y: usize,
     set: Vec<usize>,
-    rng: ThreadRng,
+    rng: StdRng,
 }
 
 impl Sidewinder {
     pub fn new(board: &mut Board) -> Self {
-        for i in 0..board.board_size - 1 {
+        Self::with_seed(board, rand::random())
+    }
+
+    pub fn with_seed(board: &mut Board, seed: u64) -> Self {
+        for i in 0..board.width - 1 {
             let cell = board.get_index(i, 0);
             let neighbor = board.get_index(i + 1, 0);
-            board.remove_wall(cell, neighbor);
+            if !board.cells[cell].masked && !board.cells[neighbor].masked {
+                board.remove_wall(cell, neighbor);
+            }
         }
         Self {
             x: 0,
             y: 1,
             set: vec![],
-            rng: rand::rng(),
+            rng: StdRng::seed_from_u64(seed),
         }
     }
 
+    /// Carve north from a random cell of the run, if one of them has an
+    /// unmasked cell above it.
     fn carve(&mut self, board: &mut Board) {
-        let selected = self.rng.random_range(0..self.set.len());
-        let index = self.set[selected];
-        let neighbor = board.get_index(board.cells[index].x, board.cells[index].y - 1);
-        board.remove_wall(index, neighbor);
+        let candidates: Vec<(usize, usize)> = self
+            .set
+            .iter()
+            .map(|&index| {
+                let cell = &board.cells[index];
+                (index, board.get_index(cell.x, cell.y - 1))
+            })
+            .filter(|&(_, neighbor)| !board.cells[neighbor].masked)
+            .collect();
+        if !candidates.is_empty() {
+            let (index, neighbor) = candidates[self.rng.random_range(0..candidates.len())];
+            board.remove_wall(index, neighbor);
+        }
         self.set.clear();
     }
 }
 
 impl Generator for Sidewinder {
     fn step(&mut self, board: &mut Board) -> State {
+        if board.width == 1 || board.height == 1 {
+            return super::corridor(board);
+        }
         let cell = board.get_index(self.x, self.y);
-        self.set.push(cell);
-        if self.x >= board.board_size - 1 {
-            self.carve(board);
-        } else if self.rng.random_bool(BOOL_TRUE_PROBABILITY) {
-            let neighbor = board.get_index(self.x + 1, self.y);
-            board.remove_wall(cell, neighbor);
-        } else {
-            self.carve(board);
+        let last = self.x >= board.width - 1;
+        // masked cells are skipped, a run ends before them
+        if !board.cells[cell].masked {
+            self.set.push(cell);
+            if last || board.cells[board.get_index(self.x + 1, self.y)].masked {
+                self.carve(board);
+            } else if self.rng.random_bool(BOOL_TRUE_PROBABILITY) {
+                let neighbor = board.get_index(self.x + 1, self.y);
+                board.remove_wall(cell, neighbor);
+            } else {
+                self.carve(board);
+            }
         }
 
-        if self.x >= board.board_size - 1 && self.y >= board.board_size - 1 {
+        if last && self.y >= board.height - 1 {
+            super::connect(board, &mut self.rng);
             State::GenerationDone
         } else {
-            if self.x == board.board_size - 1 {
+            if self.x == board.width - 1 {
                 self.x = 0;
                 self.y += 1;
             } else {
@@ -62,5 +86,5 @@ impl Generator for Sidewinder {
         }
     }
 
-    fn draw(&self, _board: &Board) {}
+    fn draw(&self, _board: &Board, _renderer: &mut dyn Renderer) {}
 }
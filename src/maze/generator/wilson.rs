@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use rand::prelude::*;
 
 use crate::{
-    maze::{Board, CURSOR_COLOR, Direction, Generator, State},
+    maze::{CURSOR_COLOR, Generator, State, render::Renderer, topology::Topology},
     raylib,
 };
 
@@ -13,23 +13,33 @@ enum IState {
 }
 
 pub struct Wilson {
-    visited: HashMap<usize, Direction>,
+    /// the cell the random walk went to from each cell
+    visited: HashMap<usize, usize>,
     current: usize,
     start: usize,
     ust: Vec<usize>,
     state: IState,
     available: Vec<usize>,
-    rng: ThreadRng,
+    rng: StdRng,
 }
 
 impl Wilson {
-    pub fn new(board: &mut Board) -> Self {
-        let mut available: Vec<usize> = (0..=(board.board_size).pow(2) - 1).collect();
-        let mut rng = rand::rng();
-        let target = rng.random_range(0..board.board_size ^ 2) as usize;
+    pub fn new<T: Topology>(board: &mut T) -> Self {
+        Self::with_seed(board, rand::random())
+    }
+
+    pub fn with_seed<T: Topology>(board: &mut T, seed: u64) -> Self {
+        let mut available = board.enabled_cells();
+        let mut rng = StdRng::seed_from_u64(seed);
+        let target = available[rng.random_range(0..available.len())];
         available.retain(|&x| x != target);
-        let start = rng.random_range(0..available.len()) as usize;
-        board.cells[target].visited = true;
+        // a single cell is already done
+        let start = if available.is_empty() {
+            target
+        } else {
+            available[rng.random_range(0..available.len())]
+        };
+        board.set_visited(target);
         Self {
             visited: HashMap::new(),
             current: start,
@@ -41,118 +51,63 @@ impl Wilson {
         }
     }
 
-    fn draw_arrow(&self, board: &Board, cell: &usize, direction: &Direction) {
-        unsafe {
-            let x = board.cells[*cell].x;
-            let y = board.cells[*cell].y;
-            let (start_pos, end_pos) = match direction {
-                Direction::North => (
-                    raylib::Vector2::new(
-                        (x * board.cell_size + board.cell_size / 2) as f32,
-                        (y * board.cell_size + board.cell_size - board.cell_size / 3) as f32,
-                    ),
-                    raylib::Vector2::new(
-                        (x * board.cell_size + board.cell_size / 2) as f32,
-                        (y * board.cell_size + board.cell_size / 3) as f32,
-                    ),
-                ),
-                Direction::South => (
-                    raylib::Vector2::new(
-                        (x * board.cell_size + board.cell_size / 2) as f32,
-                        (y * board.cell_size + board.cell_size / 3) as f32,
-                    ),
-                    raylib::Vector2::new(
-                        (x * board.cell_size + board.cell_size / 2) as f32,
-                        (y * board.cell_size + board.cell_size - board.cell_size / 3) as f32,
-                    ),
-                ),
-                Direction::East => (
-                    raylib::Vector2::new(
-                        (x * board.cell_size + board.cell_size - board.cell_size / 3) as f32,
-                        (y * board.cell_size + board.cell_size / 2) as f32,
-                    ),
-                    raylib::Vector2::new(
-                        (x * board.cell_size + board.cell_size / 3) as f32,
-                        (y * board.cell_size + board.cell_size / 2) as f32,
-                    ),
-                ),
-                Direction::West => (
-                    raylib::Vector2::new(
-                        (x * board.cell_size + board.cell_size / 3) as f32,
-                        (y * board.cell_size + board.cell_size / 2) as f32,
-                    ),
-                    raylib::Vector2::new(
-                        (x * board.cell_size + board.cell_size - board.cell_size / 3) as f32,
-                        (y * board.cell_size + board.cell_size / 2) as f32,
-                    ),
-                ),
-            };
-            // Draw the shaft of the arrow
-            raylib::DrawLineEx(start_pos, end_pos, 2.0, raylib::RED);
-
-            // Calculate the direction vector of the arrow
-            let direction: raylib::Vector2 = raylib::Vector2Subtract(end_pos, start_pos);
-            let length = raylib::Vector2Length(direction);
-
-            // If the arrow has negligible length, skip drawing the head to avoid issues
-            if length < 0.001 {
-                return;
-            };
-
-            // Normalize the direction vector
-            let norm_dir = raylib::Vector2Normalize(direction);
-
-            // Calculate the center point of the arrowhead's base
-            // This point is 'headSize' units back from 'endPos' along the arrow's direction
-            let base_center = raylib::Vector2Add(
-                end_pos,
-                raylib::Vector2Scale(norm_dir, -(board.cell_size as f32) / 10.0),
-            );
-
-            // Calculate a vector perpendicular to the arrow's direction
-            // This is used to find the two base vertices of the arrowhead triangle
-            let perp_dir = raylib::Vector2::new(-norm_dir.y, norm_dir.x); // Rotates (dx, dy) to (-dy, dx) for perpendicular 
-
-            // Calculate the two base vertices of the arrowhead triangle
-            let v1 = raylib::Vector2Add(
-                base_center,
-                raylib::Vector2Scale(perp_dir, board.cell_size as f32 / 20.0),
-            );
-            let v2 = raylib::Vector2Subtract(
-                base_center,
-                raylib::Vector2Scale(perp_dir, board.cell_size as f32 / 20.0),
-            );
-
-            // Draw the arrowhead triangle
-            raylib::DrawTriangle(v1, end_pos, v2, raylib::RED);
-        }
+    fn draw_arrow<T: Topology>(
+        &self,
+        board: &T,
+        cell: usize,
+        next: usize,
+        renderer: &mut dyn Renderer,
+    ) {
+        let center = board.center(cell);
+        let towards = board.center(next);
+        let (dx, dy) = (towards.x - center.x, towards.y - center.y);
+        let distance = (dx * dx + dy * dy).sqrt().max(0.001);
+        // a third of the cell long, centered on the cell
+        let reach = board.cell_size() / 6.0 / distance;
+        let start_pos = raylib::Vector2::new(center.x - dx * reach, center.y - dy * reach);
+        let end_pos = raylib::Vector2::new(center.x + dx * reach, center.y + dy * reach);
+        // Draw the shaft of the arrow
+        renderer.line(start_pos, end_pos, 2.0, raylib::RED);
+
+        // Calculate the direction vector of the arrow
+        let (dx, dy) = (end_pos.x - start_pos.x, end_pos.y - start_pos.y);
+        let length = (dx * dx + dy * dy).sqrt();
+
+        // If the arrow has negligible length, skip drawing the head to avoid issues
+        if length < 0.001 {
+            return;
+        };
+
+        // Normalize the direction vector
+        let (nx, ny) = (dx / length, dy / length);
+
+        // Calculate the center point of the arrowhead's base
+        // This point is 'headSize' units back from 'endPos' along the arrow's direction
+        let head = board.cell_size() / 10.0;
+        let base_center = raylib::Vector2::new(end_pos.x - nx * head, end_pos.y - ny * head);
+
+        // Calculate the two base vertices of the arrowhead triangle, using the
+        // perpendicular (-dy, dx) of the arrow direction
+        let width = board.cell_size() / 20.0;
+        let v1 = raylib::Vector2::new(base_center.x - ny * width, base_center.y + nx * width);
+        let v2 = raylib::Vector2::new(base_center.x + ny * width, base_center.y - nx * width);
+
+        // Draw the arrowhead triangle
+        renderer.triangle(v1, end_pos, v2, raylib::RED);
     }
 }
 
-impl Generator for Wilson {
-    fn step(&mut self, board: &mut Board) -> State {
+impl<T: Topology> Generator<T> for Wilson {
+    fn step(&mut self, board: &mut T) -> State {
+        if self.available.is_empty() {
+            return State::GenerationDone;
+        }
         match self.state {
             IState::Search => {
                 let last = self.current;
-                let neighbors = board.neighbors(self.current);
-                let neighbors: Vec<(usize, &Option<usize>)> = neighbors
-                    .iter()
-                    .enumerate()
-                    .filter(|(_, c)| c.is_some())
-                    .collect();
-                let index: usize = self.rng.random_range(0..neighbors.len());
-                self.current = neighbors[index].1.unwrap();
-
-                self.visited.insert(
-                    last,
-                    match neighbors[index].0 {
-                        0 => Direction::North,
-                        1 => Direction::South,
-                        2 => Direction::East,
-                        3 => Direction::West,
-                        _ => panic!("unknwon direction"),
-                    },
-                );
+                let neighbors = board.adjacent(self.current);
+                self.current = neighbors[self.rng.random_range(0..neighbors.len())];
+                self.visited.insert(last, self.current);
 
                 if self.ust.contains(&self.current) {
                     self.current = self.start;
@@ -163,27 +118,9 @@ impl Generator for Wilson {
                 let last = self.current;
                 self.ust.push(self.current);
                 self.available.retain(|&x| x != self.current);
-                let neighbors = board.neighbors(self.current);
-                let direction = self.visited.get(&self.current);
-                if let Some(direction) = direction {
-                    match direction {
-                        Direction::North => {
-                            board.remove_wall(last, neighbors[0].unwrap());
-                            self.current = neighbors[0].unwrap();
-                        }
-                        Direction::South => {
-                            board.remove_wall(last, neighbors[1].unwrap());
-                            self.current = neighbors[1].unwrap();
-                        }
-                        Direction::East => {
-                            board.remove_wall(last, neighbors[2].unwrap());
-                            self.current = neighbors[2].unwrap();
-                        }
-                        Direction::West => {
-                            board.remove_wall(last, neighbors[3].unwrap());
-                            self.current = neighbors[3].unwrap();
-                        }
-                    }
+                if let Some(&next) = self.visited.get(&self.current) {
+                    board.remove_wall(last, next);
+                    self.current = next;
                 }
 
                 if self.ust.contains(&self.current) {
@@ -200,27 +137,19 @@ impl Generator for Wilson {
         State::Generate
     }
 
-    fn draw(&self, board: &Board) {
-        unsafe {
-            raylib::DrawCircle(
-                (board.x + board.cells[self.start].x * board.cell_size + board.cell_size / 2)
-                    as i32,
-                (board.y + board.cells[self.start].y * board.cell_size + board.cell_size / 2)
-                    as i32,
-                board.cell_size as f32 / 4.0,
-                raylib::WHITE,
-            );
-            raylib::DrawCircle(
-                (board.x + board.cells[self.current].x * board.cell_size + board.cell_size / 2)
-                    as i32,
-                (board.y + board.cells[self.current].y * board.cell_size + board.cell_size / 2)
-                    as i32,
-                board.cell_size as f32 / 4.0,
-                CURSOR_COLOR,
-            );
-            for (c, d) in &self.visited {
-                self.draw_arrow(board, c, d);
-            }
+    fn draw(&self, board: &T, renderer: &mut dyn Renderer) {
+        renderer.circle(
+            board.center(self.start),
+            board.cell_size() / 4.0,
+            raylib::WHITE,
+        );
+        renderer.circle(
+            board.center(self.current),
+            board.cell_size() / 4.0,
+            CURSOR_COLOR,
+        );
+        for (&cell, &next) in &self.visited {
+            self.draw_arrow(board, cell, next, renderer);
         }
     }
 }
@@ -0,0 +1,106 @@
+use std::time::{Duration, Instant};
+
+use crate::maze::{Board, CELL_SIZE, Generator, Solver, State};
+
+/// Numbers collected while running a generator to completion.
+#[derive(Clone, Debug, Default)]
+pub struct GenerationStats {
+    pub steps: usize,
+    pub elapsed: Duration,
+}
+
+/// Numbers collected while running a solver.
+#[derive(Clone, Debug, Default)]
+pub struct SolveStats {
+    pub steps: usize,
+    pub elapsed: Duration,
+    pub solved: bool,
+    /// why the solver gave up, if it did
+    pub error: Option<String>,
+}
+
+/// Build a `width` x `height` board, create the generator with `init` and
+/// step it until it reports `State::GenerationDone`. No window is needed.
+pub fn generate<G, F>(width: usize, height: usize, init: F) -> (Board, GenerationStats)
+where
+    G: Generator,
+    F: FnOnce(&mut Board) -> G,
+{
+    let mut board = Board::new(0, width, height, CELL_SIZE);
+    let mut generator = init(&mut board);
+    let stats = run(&mut board, &mut generator);
+    (board, stats)
+}
+
+/// Step an already constructed generator on `board` until it is done. Works
+/// on any grid, e.g. a `HexBoard`.
+pub fn run<T: ?Sized>(board: &mut T, generator: &mut dyn Generator<T>) -> GenerationStats {
+    let start = Instant::now();
+    let mut steps = 0;
+    loop {
+        steps += 1;
+        if generator.step(board) == State::GenerationDone {
+            break;
+        }
+    }
+    GenerationStats {
+        steps,
+        elapsed: start.elapsed(),
+    }
+}
+
+/// Step `solver` on `board` until it reaches the goal, reports an error or
+/// `limit` steps have passed. Walkers that never give up are stopped by the
+/// limit.
+pub fn solve<T: ?Sized>(board: &T, solver: &mut dyn Solver<T>, limit: usize) -> SolveStats {
+    let start = Instant::now();
+    let mut steps = 0;
+    let error = loop {
+        steps += 1;
+        match solver.step(board) {
+            Ok(State::Done) => break None,
+            Ok(_) if steps >= limit => break Some(format!("no solution after {} steps", limit)),
+            Ok(_) => {}
+            Err(err) => break Some(err),
+        }
+    };
+    SolveStats {
+        steps,
+        elapsed: start.elapsed(),
+        solved: error.is_none(),
+        error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::{generator::backtracking::Backtracking, solver::bfs::Bfs};
+
+    #[test]
+    fn generate_and_solve() {
+        let (board, stats) = generate(12, 9, |_| Backtracking::with_seed(4));
+        // one step per carved cell, one per backtrack and the last one
+        assert_eq!(stats.steps, 2 * board.cells.len());
+        let (again, again_stats) = generate(12, 9, |_| Backtracking::with_seed(4));
+        assert_eq!(again, board);
+        assert_eq!(again_stats.steps, stats.steps);
+
+        let mut solver = Bfs::new(&board);
+        let stats = solve(&board, &mut solver, 1000);
+        assert!(stats.solved);
+        assert_eq!(stats.error, None);
+        let path = solver.get_path();
+        assert_eq!(path.first(), Some(&board.start));
+        assert_eq!(path.last(), Some(&board.goal));
+    }
+
+    #[test]
+    fn solve_stops_at_the_limit() {
+        let (board, _) = generate(12, 9, |_| Backtracking::with_seed(4));
+        let stats = solve(&board, &mut Bfs::new(&board), 3);
+        assert!(!stats.solved);
+        assert_eq!(stats.steps, 3);
+        assert!(stats.error.is_some());
+    }
+}
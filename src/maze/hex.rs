@@ -0,0 +1,316 @@
+use crate::{
+    maze::{
+        render::{Renderer, Style},
+        topology::Topology,
+    },
+    raylib::{Rectangle, Vector2},
+};
+
+/// Number of sides of a hex cell.
+pub const SIDES: usize = 6;
+
+#[derive(Clone, Debug)]
+pub struct HexCell {
+    pub x: usize,
+    pub y: usize,
+    pub visited: bool,
+    /// walls in the order east, north east, north west, west, south west,
+    /// south east
+    pub walls: [bool; SIDES],
+}
+
+impl HexCell {
+    pub fn new(x: usize, y: usize) -> Self {
+        Self {
+            x,
+            y,
+            visited: false,
+            walls: [true; SIDES],
+        }
+    }
+}
+
+/// A grid of pointy topped hexagons. Odd rows are shifted half a cell to
+/// the right, so the neighbors above and below depend on the row.
+#[derive(Clone, Debug)]
+pub struct HexBoard {
+    pub cells: Vec<HexCell>,
+    pub width: usize,
+    pub height: usize,
+    pub start: usize,
+    pub goal: usize,
+    /// distance between the centers of two neighbors
+    pub cell_size: usize,
+    pub x: usize,
+    pub y: usize,
+}
+
+impl HexBoard {
+    pub fn new(border: usize, width: usize, height: usize, cell_size: usize) -> Self {
+        let mut cells = Vec::with_capacity(width * height);
+        for x in 0..width {
+            for y in 0..height {
+                cells.push(HexCell::new(x, y));
+            }
+        }
+        let mut board = Self {
+            cells,
+            width,
+            height,
+            start: 0,
+            goal: width * height - 1,
+            cell_size,
+            x: border,
+            y: border,
+        };
+        // the entrance is on the west side, the exit on the east side
+        board.cells[board.start].walls[3] = false;
+        let goal = board.goal;
+        board.cells[goal].walls[0] = false;
+        board
+    }
+
+    pub fn get_index(&self, x: usize, y: usize) -> usize {
+        x * self.height + y
+    }
+
+    /**
+     * return the neighbors in the order of `HexCell::walls`
+     */
+    pub fn neighbors(&self, index: usize) -> [Option<usize>; SIDES] {
+        let cell = &self.cells[index];
+        let (x, y) = (cell.x as isize, cell.y as isize);
+        let shift = (cell.y % 2) as isize;
+        let offsets = [
+            (1, 0),
+            (shift, -1),
+            (shift - 1, -1),
+            (-1, 0),
+            (shift - 1, 1),
+            (shift, 1),
+        ];
+        offsets.map(|(dx, dy)| {
+            let (nx, ny) = (x + dx, y + dy);
+            if nx < 0 || ny < 0 || nx >= self.width as isize || ny >= self.height as isize {
+                None
+            } else {
+                Some(self.get_index(nx as usize, ny as usize))
+            }
+        })
+    }
+
+    /// The side of `cell` that faces `neighbor`.
+    fn side(&self, cell: usize, neighbor: usize) -> usize {
+        self.neighbors(cell)
+            .iter()
+            .position(|&other| other == Some(neighbor))
+            .expect("cells are not neighbors")
+    }
+
+    fn radius(&self) -> f32 {
+        self.cell_size as f32 / 3f32.sqrt()
+    }
+
+    /// Corner `index` of the cell, counter clockwise starting at the top of
+    /// the east side. Side `s` lies between corner `s - 1` and `s`.
+    fn corner(&self, cell: usize, index: usize) -> Vector2 {
+        let center = self.center(cell);
+        let angle = (30.0 + 60.0 * index as f32).to_radians();
+        let radius = self.radius();
+        Vector2::new(
+            center.x + radius * angle.cos(),
+            center.y - radius * angle.sin(),
+        )
+    }
+
+    /**
+     * check that both sides of every shared wall agree
+     */
+    pub fn check_walls(&self) -> Result<(), String> {
+        for (index, cell) in self.cells.iter().enumerate() {
+            for (side, neighbor) in self.neighbors(index).into_iter().enumerate() {
+                if let Some(neighbor) = neighbor
+                    && cell.walls[side] != self.cells[neighbor].walls[(side + 3) % SIDES]
+                {
+                    let other = &self.cells[neighbor];
+                    return Err(format!(
+                        "wall between ({}, {}) and ({}, {}) does not match",
+                        cell.x, cell.y, other.x, other.y
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Topology for HexBoard {
+    fn cell_count(&self) -> usize {
+        self.cells.len()
+    }
+
+    fn adjacent(&self, cell: usize) -> Vec<usize> {
+        self.neighbors(cell).into_iter().flatten().collect()
+    }
+
+    fn passages(&self, cell: usize) -> Vec<usize> {
+        let walls = &self.cells[cell].walls;
+        self.neighbors(cell)
+            .into_iter()
+            .zip(walls)
+            .filter_map(|(neighbor, wall)| neighbor.filter(|_| !wall))
+            .collect()
+    }
+
+    fn remove_wall(&mut self, cell: usize, neighbor: usize) {
+        let side = self.side(cell, neighbor);
+        self.cells[cell].walls[side] = false;
+        self.cells[neighbor].walls[(side + 3) % SIDES] = false;
+        self.cells[cell].visited = true;
+        self.cells[neighbor].visited = true;
+    }
+
+    fn visited(&self, cell: usize) -> bool {
+        self.cells[cell].visited
+    }
+
+    fn set_visited(&mut self, cell: usize) {
+        self.cells[cell].visited = true;
+    }
+
+    fn start(&self) -> usize {
+        self.start
+    }
+
+    fn goal(&self) -> usize {
+        self.goal
+    }
+
+    fn center(&self, cell: usize) -> Vector2 {
+        let cell = &self.cells[cell];
+        let size = self.cell_size as f32;
+        let shift = if cell.y % 2 == 1 { 0.5 } else { 0.0 };
+        Vector2::new(
+            self.x as f32 + size * (cell.x as f32 + 0.5 + shift),
+            self.y as f32 + self.radius() * (1.0 + 1.5 * cell.y as f32),
+        )
+    }
+
+    fn cell_size(&self) -> f32 {
+        self.cell_size as f32
+    }
+
+    fn bounds(&self) -> Rectangle {
+        let size = self.cell_size as f32;
+        let shift = if self.height > 1 { 0.5 } else { 0.0 };
+        Rectangle::new(
+            self.x as f32,
+            self.y as f32,
+            size * (self.width as f32 + shift),
+            self.radius() * (0.5 + 1.5 * self.height as f32),
+        )
+    }
+
+    fn draw_board(&self, style: &Style, renderer: &mut dyn Renderer) {
+        for (index, cell) in self.cells.iter().enumerate() {
+            if !cell.visited && style.unvisited_color.a > 0 {
+                let center = self.center(index);
+                for corner in 0..SIDES {
+                    renderer.triangle(
+                        center,
+                        self.corner(index, corner),
+                        self.corner(index, (corner + 1) % SIDES),
+                        style.unvisited_color,
+                    );
+                }
+            }
+            // shared walls are drawn by the cell with the lower index
+            for (side, neighbor) in self.neighbors(index).into_iter().enumerate() {
+                if cell.walls[side] && neighbor.is_none_or(|neighbor| neighbor > index) {
+                    renderer.line(
+                        self.corner(index, (side + SIDES - 1) % SIDES),
+                        self.corner(index, side),
+                        style.wall_width,
+                        style.wall_color,
+                    );
+                }
+            }
+        }
+        for (index, color) in [
+            (self.start, style.start_color),
+            (self.goal, style.goal_color),
+        ] {
+            renderer.circle(self.center(index), self.cell_size as f32 / 4.0, color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::{
+        Solver, analysis, generator, headless,
+        solver::{bfs::Bfs, djikstra::Djikstra},
+    };
+
+    #[test]
+    fn neighbors_shift_with_the_row() {
+        let board = HexBoard::new(0, 5, 5, 20);
+        let at = |x, y| Some(board.get_index(x, y));
+        // even rows lean left, odd rows right
+        assert_eq!(
+            board.neighbors(board.get_index(2, 2)),
+            [at(3, 2), at(2, 1), at(1, 1), at(1, 2), at(1, 3), at(2, 3)]
+        );
+        assert_eq!(
+            board.neighbors(board.get_index(2, 1)),
+            [at(3, 1), at(3, 0), at(2, 0), at(1, 1), at(2, 2), at(3, 2)]
+        );
+        for index in 0..board.cells.len() {
+            for (side, neighbor) in board.neighbors(index).into_iter().enumerate() {
+                if let Some(neighbor) = neighbor {
+                    assert_eq!(board.neighbors(neighbor)[(side + 3) % SIDES], Some(index));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn edges_do_not_wrap() {
+        let board = HexBoard::new(0, 5, 4, 20);
+        let at = |x, y| Some(board.get_index(x, y));
+        assert_eq!(
+            board.neighbors(board.get_index(0, 0)),
+            [at(1, 0), None, None, None, None, at(0, 1)]
+        );
+        assert_eq!(
+            board.neighbors(board.get_index(4, 3)),
+            [None, None, at(4, 2), at(3, 3), None, None]
+        );
+        assert_eq!(board.adjacent(board.get_index(0, 2)).len(), 3);
+    }
+
+    #[test]
+    fn generators_make_perfect_mazes() {
+        for name in generator::ALGORITHMS {
+            let mut board = HexBoard::new(0, 8, 6, 20);
+            let Some(mut generator) = generator::for_topology(name, &mut board, 5) else {
+                continue;
+            };
+            headless::run(&mut board, generator.as_mut());
+            assert!(analysis::is_perfect(&board), "{name}");
+            assert_eq!(board.check_walls(), Ok(()), "{name}");
+
+            let mut bfs = Bfs::new(&board);
+            assert!(headless::solve(&board, &mut bfs, 1000).solved, "{name}");
+            let mut djikstra = Djikstra::new(&board);
+            assert!(
+                headless::solve(&board, &mut djikstra, 1000).solved,
+                "{name}"
+            );
+            // the weights count the start as one
+            assert_eq!(djikstra.weights[board.goal], Some(bfs.get_path().len()));
+            assert_eq!(djikstra.get_path().len(), bfs.get_path().len());
+        }
+    }
+}
@@ -0,0 +1,309 @@
+use crate::{
+    maze::{
+        Board, path,
+        render::{Renderer, Style},
+        topology::Topology,
+    },
+    raylib::{Color, Rectangle, Vector2},
+};
+
+/// A stack of boards of the same size. A cell is linked to the cell at the
+/// same position on the level above and below by stairs, which are closed
+/// until a generator removes the wall between them. The maze is entered on
+/// the first level and left on the last one. The levels are drawn side by
+/// side, one cell apart.
+#[derive(Clone, Debug)]
+pub struct LayeredBoard {
+    pub levels: Vec<Board>,
+    /// `ceilings[level][cell]` is the wall between the cell and the one
+    /// above it on the next level
+    pub ceilings: Vec<Vec<bool>>,
+    pub start: usize,
+    pub goal: usize,
+}
+
+impl LayeredBoard {
+    pub fn new(
+        border: usize,
+        width: usize,
+        height: usize,
+        levels: usize,
+        cell_size: usize,
+    ) -> Self {
+        let boards: Vec<Board> = (0..levels)
+            .map(|level| {
+                let mut board = Board::new(border, width, height, cell_size);
+                board.x = border + level * (width + 1) * cell_size;
+                // only the first and last level lead outside
+                board.set_opening(board.start, level > 0);
+                board.set_opening(board.goal, level + 1 < levels);
+                board
+            })
+            .collect();
+        Self {
+            ceilings: vec![vec![true; width * height]; levels.saturating_sub(1)],
+            start: 0,
+            goal: levels * width * height - 1,
+            levels: boards,
+        }
+    }
+
+    /// Number of cells on one level.
+    pub fn level_size(&self) -> usize {
+        self.levels[0].cells.len()
+    }
+
+    /// The level and the index on that level of a cell.
+    pub fn locate(&self, index: usize) -> (usize, usize) {
+        (index / self.level_size(), index % self.level_size())
+    }
+
+    pub fn get_index(&self, level: usize, cell: usize) -> usize {
+        level * self.level_size() + cell
+    }
+
+    /// The cell on the level above, if there is one.
+    pub fn up(&self, index: usize) -> Option<usize> {
+        let (level, cell) = self.locate(index);
+        (level + 1 < self.levels.len()).then(|| self.get_index(level + 1, cell))
+    }
+
+    /// The cell on the level below, if there is one.
+    pub fn down(&self, index: usize) -> Option<usize> {
+        let (level, cell) = self.locate(index);
+        (level > 0).then(|| self.get_index(level - 1, cell))
+    }
+
+    /**
+     * check the walls of every level
+     */
+    pub fn check_walls(&self) -> Result<(), String> {
+        for (level, board) in self.levels.iter().enumerate() {
+            board
+                .check_walls()
+                .map_err(|err| format!("level {}: {}", level + 1, err))?;
+        }
+        Ok(())
+    }
+
+    /// Mark the open stairs with a triangle pointing up or down.
+    fn draw_stairs(&self, style: &Style, renderer: &mut dyn Renderer) {
+        for (level, board) in self.levels.iter().enumerate() {
+            let size = board.cell_size as f32;
+            for (index, cell) in board.cells.iter().enumerate() {
+                let x = (board.x + cell.x * board.cell_size) as f32 + size / 2.0;
+                let y = (board.y + cell.y * board.cell_size) as f32;
+                if level + 1 < self.levels.len() && !self.ceilings[level][index] {
+                    renderer.triangle(
+                        Vector2::new(x, y + size * 0.1),
+                        Vector2::new(x - size * 0.2, y + size * 0.45),
+                        Vector2::new(x + size * 0.2, y + size * 0.45),
+                        style.stairs_color,
+                    );
+                }
+                if level > 0 && !self.ceilings[level - 1][index] {
+                    renderer.triangle(
+                        Vector2::new(x, y + size * 0.9),
+                        Vector2::new(x + size * 0.2, y + size * 0.55),
+                        Vector2::new(x - size * 0.2, y + size * 0.55),
+                        style.stairs_color,
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl Topology for LayeredBoard {
+    fn cell_count(&self) -> usize {
+        self.levels.len() * self.level_size()
+    }
+
+    fn adjacent(&self, cell: usize) -> Vec<usize> {
+        let (level, index) = self.locate(cell);
+        self.levels[level]
+            .neighbors(index)
+            .into_iter()
+            .flatten()
+            .map(|neighbor| self.get_index(level, neighbor))
+            .chain(self.down(cell))
+            .chain(self.up(cell))
+            .collect()
+    }
+
+    fn passages(&self, cell: usize) -> Vec<usize> {
+        let (level, index) = self.locate(cell);
+        let mut passages: Vec<usize> = self.levels[level]
+            .passages(index)
+            .into_iter()
+            .map(|neighbor| self.get_index(level, neighbor))
+            .collect();
+        if let Some(down) = self.down(cell)
+            && !self.ceilings[level - 1][index]
+        {
+            passages.push(down);
+        }
+        if let Some(up) = self.up(cell)
+            && !self.ceilings[level][index]
+        {
+            passages.push(up);
+        }
+        passages
+    }
+
+    fn remove_wall(&mut self, cell: usize, neighbor: usize) {
+        let (level, index) = self.locate(cell);
+        let (other_level, other_index) = self.locate(neighbor);
+        if level == other_level {
+            self.levels[level].remove_wall(index, other_index);
+            return;
+        }
+        assert!(
+            index == other_index && level.abs_diff(other_level) == 1,
+            "cells are not neighbors"
+        );
+        self.ceilings[level.min(other_level)][index] = false;
+        self.levels[level].cells[index].visited = true;
+        self.levels[other_level].cells[index].visited = true;
+    }
+
+    fn visited(&self, cell: usize) -> bool {
+        let (level, index) = self.locate(cell);
+        self.levels[level].cells[index].visited
+    }
+
+    fn set_visited(&mut self, cell: usize) {
+        let (level, index) = self.locate(cell);
+        self.levels[level].cells[index].visited = true;
+    }
+
+    fn start(&self) -> usize {
+        self.start
+    }
+
+    fn goal(&self) -> usize {
+        self.goal
+    }
+
+    fn center(&self, cell: usize) -> Vector2 {
+        let (level, index) = self.locate(cell);
+        self.levels[level].center(index)
+    }
+
+    fn cell_size(&self) -> f32 {
+        self.levels[0].cell_size as f32
+    }
+
+    fn bounds(&self) -> Rectangle {
+        let first = self.levels[0].bounds();
+        let last = self.levels[self.levels.len() - 1].bounds();
+        Rectangle::new(
+            first.x,
+            first.y,
+            last.x + last.width - first.x,
+            first.height,
+        )
+    }
+
+    fn draw_board(&self, style: &Style, renderer: &mut dyn Renderer) {
+        // the levels do not mark their own start and goal
+        let none = Color {
+            r: 0,
+            g: 0,
+            b: 0,
+            a: 0,
+        };
+        let level_style = Style {
+            start_color: none,
+            goal_color: none,
+            ..style.clone()
+        };
+        for board in &self.levels {
+            board.draw_styled(renderer, &level_style);
+        }
+        self.draw_stairs(style, renderer);
+        for (index, color) in [
+            (self.start, style.start_color),
+            (self.goal, style.goal_color),
+        ] {
+            renderer.circle(self.center(index), self.cell_size() / 4.0, color);
+        }
+    }
+
+    /// Draw the parts of the path on every level, the stairs connect them.
+    fn draw_path(&self, path: &[usize], style: &Style, renderer: &mut dyn Renderer) {
+        for run in path.chunk_by(|&a, &b| self.locate(a).0 == self.locate(b).0) {
+            let level = self.locate(run[0]).0;
+            if run.len() == 1 {
+                renderer.circle(
+                    self.center(run[0]),
+                    self.cell_size() / 10.0,
+                    style.path_color,
+                );
+                continue;
+            }
+            let cells: Vec<usize> = run.iter().map(|&cell| self.locate(cell).1).collect();
+            path::draw_path_styled(&self.levels[level], &cells, style, renderer);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::{CELL_SIZE, analysis, generator, headless, solver::bfs::Bfs};
+
+    #[test]
+    fn stairs_link_the_levels() {
+        let mut board = LayeredBoard::new(0, 4, 3, 3, CELL_SIZE);
+        assert_eq!(board.cell_count(), 36);
+        let cell = board.get_index(1, 5);
+        assert_eq!(board.locate(cell), (1, 5));
+        assert_eq!((board.down(cell), board.up(cell)), (Some(5), Some(29)));
+        assert_eq!(board.up(29), None);
+        assert_eq!(board.down(5), None);
+        assert!(board.adjacent(cell).contains(&5) && board.adjacent(cell).contains(&29));
+
+        assert!(board.passages(cell).is_empty());
+        board.remove_wall(cell, 29);
+        assert!(!board.ceilings[1][5]);
+        assert_eq!(board.passages(cell), [29]);
+        assert_eq!(board.passages(29), [cell]);
+
+        // only the first level has an entrance and only the last an exit
+        let outside = |level: usize, index: usize| {
+            let walls = &board.levels[level].cells[index].walls;
+            !walls.left || !walls.right
+        };
+        assert!(outside(0, 0) && !outside(1, 0) && !outside(2, 0));
+        assert!(!outside(0, 11) && !outside(1, 11) && outside(2, 11));
+    }
+
+    #[test]
+    fn generators_reach_every_level() {
+        for name in generator::ALGORITHMS {
+            let mut board = LayeredBoard::new(0, 6, 5, 3, CELL_SIZE);
+            let Some(mut generator) = generator::for_topology(name, &mut board, 7) else {
+                continue;
+            };
+            headless::run(&mut board, generator.as_mut());
+            assert!(analysis::is_perfect(&board), "{name}");
+            assert_eq!(board.check_walls(), Ok(()), "{name}");
+            // a perfect maze over all levels has stairs between every two
+            for (level, ceiling) in board.ceilings.iter().enumerate() {
+                assert!(ceiling.contains(&false), "{name} level {level}");
+            }
+            assert!(
+                analysis::distances(&board, board.start)
+                    .iter()
+                    .all(Option::is_some),
+                "{name}"
+            );
+
+            let mut bfs = Bfs::new(&board);
+            assert!(headless::solve(&board, &mut bfs, 1000).solved, "{name}");
+            let levels: Vec<usize> = bfs.path.iter().map(|&cell| board.locate(cell).0).collect();
+            assert_eq!((levels[0], levels[levels.len() - 1]), (0, 2), "{name}");
+        }
+    }
+}
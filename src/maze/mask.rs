@@ -0,0 +1,210 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Seek},
+    path::Path,
+};
+
+/// The shape of a maze: which cells of a board take part in it. Cells that
+/// are not enabled are masked, the maze is built around them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mask {
+    pub width: usize,
+    pub height: usize,
+    /// in the order of `Board::cells`
+    enabled: Vec<bool>,
+}
+
+impl Mask {
+    /// A mask with every cell enabled.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            enabled: vec![true; width * height],
+        }
+    }
+
+    pub fn is_enabled(&self, x: usize, y: usize) -> bool {
+        self.enabled[x * self.height + y]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, enabled: bool) {
+        self.enabled[x * self.height + y] = enabled;
+    }
+
+    /// Number of enabled cells.
+    pub fn count(&self) -> usize {
+        self.enabled.iter().filter(|&&enabled| enabled).count()
+    }
+
+    /**
+     * Read a mask from text with one character per cell: spaces and dots
+     * are masked, every other character is part of the maze. Short lines
+     * are padded with masked cells.
+     *
+     * ```text
+     * ..###..
+     * .#####.
+     * #######
+     * ```
+     */
+    pub fn from_ascii(text: &str) -> Result<Self, String> {
+        let lines: Vec<Vec<char>> = text
+            .trim_end()
+            .lines()
+            .map(|line| line.trim_end_matches('\r').chars().collect())
+            .collect();
+        let width = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+        if width == 0 {
+            return Err(String::from("the mask is empty"));
+        }
+        let mut mask = Self::new(width, lines.len());
+        for x in 0..width {
+            for (y, line) in lines.iter().enumerate() {
+                let enabled = line.get(x).is_some_and(|&c| c != ' ' && c != '.');
+                mask.set(x, y, enabled);
+            }
+        }
+        Ok(mask)
+    }
+
+    /**
+     * Read a mask from a png image with one pixel per cell: dark pixels
+     * are part of the maze, light and transparent ones are masked.
+     */
+    pub fn from_png<R: BufRead + Seek>(reader: R) -> Result<Self, String> {
+        let mut decoder = png::Decoder::new(reader);
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder.read_info().map_err(|err| err.to_string())?;
+        let size = reader
+            .output_buffer_size()
+            .ok_or_else(|| String::from("the image is too large"))?;
+        let mut buffer = vec![0; size];
+        let info = reader
+            .next_frame(&mut buffer)
+            .map_err(|err| err.to_string())?;
+
+        let (width, height) = (info.width as usize, info.height as usize);
+        let samples = info.color_type.samples();
+        let mut mask = Self::new(width, height);
+        for y in 0..height {
+            let line = &buffer[y * info.line_size..];
+            for x in 0..width {
+                let pixel = &line[x * samples..(x + 1) * samples];
+                let (luma, alpha) = match pixel {
+                    [gray] => (*gray as u32, 255),
+                    [gray, alpha] => (*gray as u32, *alpha),
+                    [r, g, b] => ((*r as u32 + *g as u32 + *b as u32) / 3, 255),
+                    [r, g, b, alpha] => ((*r as u32 + *g as u32 + *b as u32) / 3, *alpha),
+                    _ => return Err(String::from("unsupported color type")),
+                };
+                mask.set(x, y, alpha >= 128 && luma < 128);
+            }
+        }
+        Ok(mask)
+    }
+
+    /**
+     * Load a mask from a png image or, for every other file, from text.
+     */
+    pub fn load<P: AsRef<Path>>(file: P) -> Result<Self, String> {
+        let file = file.as_ref();
+        let error = |err: std::io::Error| format!("{}: {}", file.display(), err);
+        if file.extension().is_some_and(|ext| ext == "png") {
+            Self::from_png(BufReader::new(File::open(file).map_err(error)?))
+                .map_err(|err| format!("{}: {}", file.display(), err))
+        } else {
+            Self::from_ascii(&std::fs::read_to_string(file).map_err(error)?)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::maze::{Board, CELL_SIZE, Walls, analysis, generator, headless, solver::bfs::Bfs};
+
+    const SHAPE: &str = "\
+..###..
+.#####.
+#######
+";
+
+    #[test]
+    fn from_ascii() {
+        let mask = Mask::from_ascii(SHAPE).unwrap();
+        assert_eq!((mask.width, mask.height, mask.count()), (7, 3, 15));
+        assert!(!mask.is_enabled(1, 0) && mask.is_enabled(2, 0) && mask.is_enabled(0, 2));
+
+        // short lines are padded with masked cells
+        let mask = Mask::from_ascii("###\n#\n").unwrap();
+        assert_eq!((mask.width, mask.count()), (3, 4));
+        assert!(!mask.is_enabled(2, 1));
+        assert!(Mask::from_ascii(" \n\n").is_err());
+    }
+
+    #[test]
+    fn from_png() {
+        // dark and opaque pixels are enabled
+        let pixels: [[u8; 4]; 4] = [
+            [0, 0, 0, 255],
+            [255, 255, 255, 255],
+            [20, 20, 20, 0],
+            [90, 40, 60, 255],
+        ];
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, 2, 2);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(pixels.as_flattened()).unwrap();
+        writer.finish().unwrap();
+
+        let mask = Mask::from_png(Cursor::new(png)).unwrap();
+        assert_eq!((mask.width, mask.height), (2, 2));
+        assert!(mask.is_enabled(0, 0) && !mask.is_enabled(1, 0));
+        assert!(!mask.is_enabled(0, 1) && mask.is_enabled(1, 1));
+        assert!(Mask::from_png(Cursor::new(b"not a png".to_vec())).is_err());
+    }
+
+    #[test]
+    fn board_in_the_shape_of_the_mask() {
+        let mask = Mask::from_ascii(SHAPE).unwrap();
+        let board = Board::with_mask(0, &mask, CELL_SIZE).unwrap();
+        assert_eq!(board.start, board.get_index(0, 2));
+        assert_eq!(board.goal, board.get_index(6, 2));
+        assert!(!board.cells[board.start].walls.left && !board.cells[board.goal].walls.right);
+        // the corners of the board are masked and closed again
+        assert!(board.cells[0].masked && board.cells[0].walls.left);
+        assert_eq!(board.neighbors(board.get_index(2, 0))[2], None);
+
+        let mut apart = Mask::new(3, 1);
+        apart.set(1, 0, false);
+        assert!(Board::with_mask(0, &apart, CELL_SIZE).is_err());
+        let empty = Mask {
+            width: 2,
+            height: 2,
+            enabled: vec![false; 4],
+        };
+        assert!(Board::with_mask(0, &empty, CELL_SIZE).is_err());
+    }
+
+    #[test]
+    fn generators_keep_out_of_masked_cells() {
+        let mask = Mask::from_ascii(SHAPE).unwrap();
+        for name in generator::ALGORITHMS {
+            let mut board = Board::with_mask(0, &mask, CELL_SIZE).unwrap();
+            let mut generator = generator::by_name(name, &mut board, 3).unwrap();
+            headless::run(&mut board, generator.as_mut());
+            assert!(analysis::is_perfect(&board), "{name}");
+            for cell in board.cells.iter().filter(|cell| cell.masked) {
+                assert_eq!(cell.walls, Walls::default(), "{name}");
+            }
+            let mut bfs = Bfs::new(&board);
+            assert!(headless::solve(&board, &mut bfs, 1000).solved, "{name}");
+            assert!(bfs.path.iter().all(|&cell| !board.cells[cell].masked));
+        }
+    }
+}
@@ -1,12 +1,34 @@
+pub mod analysis;
+pub mod ascii;
+pub mod braid;
+pub mod delta;
 pub mod generator;
+pub mod headless;
+pub mod hex;
+pub mod layered;
+pub mod mask;
 pub mod path;
+pub mod polar;
+pub mod raster;
+pub mod render;
 pub mod solver;
+pub mod storage;
+pub mod svg;
+pub mod topology;
 
 use std::fmt;
 
-use crate::raylib;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, PartialEq, Eq)]
+use crate::{
+    maze::{
+        mask::Mask,
+        render::{Renderer, Style},
+    },
+    raylib,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Direction {
     North,
     South,
@@ -14,6 +36,40 @@ pub enum Direction {
     West,
 }
 
+impl Direction {
+    pub fn turn_left(self) -> Self {
+        match self {
+            Direction::North => Direction::West,
+            Direction::West => Direction::South,
+            Direction::South => Direction::East,
+            Direction::East => Direction::North,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        self.turn_left().turn_left().turn_left()
+    }
+
+    pub fn reverse(self) -> Self {
+        self.turn_left().turn_left()
+    }
+}
+
+/// Which edges of a board wrap around to the opposite edge.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Wrap {
+    #[default]
+    None,
+    /// the left and right edge meet
+    Cylinder,
+    /// both pairs of edges meet
+    Torus,
+}
+
+/// Cell size of boards built without a window, by the headless runner and
+/// when a maze is loaded. It only matters once they are exported.
+pub const CELL_SIZE: usize = 10;
+
 pub const WALL_COLOR: raylib::Color = raylib::Color {
     r: 100,
     g: 100,
@@ -32,6 +88,30 @@ pub const CURSOR_COLOR: raylib::Color = raylib::Color {
     b: 17,
     a: 255,
 };
+pub const START_COLOR: raylib::Color = raylib::Color {
+    r: 0,
+    g: 120,
+    b: 255,
+    a: 160,
+};
+pub const GOAL_COLOR: raylib::Color = raylib::Color {
+    r: 255,
+    g: 160,
+    b: 0,
+    a: 160,
+};
+pub const STAIRS_COLOR: raylib::Color = raylib::Color {
+    r: 200,
+    g: 120,
+    b: 255,
+    a: 255,
+};
+pub const WRAP_COLOR: raylib::Color = raylib::Color {
+    r: 0,
+    g: 200,
+    b: 200,
+    a: 255,
+};
 
 #[derive(Copy, Clone, Eq, PartialEq)]
 pub enum State {
@@ -54,18 +134,38 @@ impl fmt::Display for State {
     }
 }
 
-pub trait Generator {
-    fn step(&mut self, board: &mut Board) -> State;
-    fn draw(&self, board: &Board);
+/// Builds a maze on a grid `T`, one step at a time. Most generators only
+/// work on the square `Board`, the ones written against `Topology` work on
+/// every grid.
+pub trait Generator<T: ?Sized = Board> {
+    fn step(&mut self, board: &mut T) -> State;
+    fn draw(&self, board: &T, renderer: &mut dyn Renderer);
 }
 
-pub trait Solver {
-    fn step(&mut self, board: &Board) -> Result<State, String>;
+impl<T: ?Sized, G: Generator<T> + ?Sized> Generator<T> for Box<G> {
+    fn step(&mut self, board: &mut T) -> State {
+        (**self).step(board)
+    }
+
+    fn draw(&self, board: &T, renderer: &mut dyn Renderer) {
+        (**self).draw(board, renderer)
+    }
+}
+
+pub trait Solver<T: ?Sized = Board> {
+    fn step(&mut self, board: &T) -> Result<State, String>;
     fn get_path(&self) -> &Vec<usize>;
-    fn draw(&self, board: &Board);
+    /**
+     * number of cells the search has reached so far, for solvers that
+     * keep track of it
+     */
+    fn expanded(&self) -> Option<usize> {
+        None
+    }
+    fn draw(&self, board: &T, renderer: &mut dyn Renderer);
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Walls {
     pub left: bool,
     pub right: bool,
@@ -73,6 +173,20 @@ pub struct Walls {
     pub bottom: bool,
 }
 
+impl Walls {
+    /**
+     * whether the wall on the given side is set
+     */
+    pub fn has(&self, direction: Direction) -> bool {
+        match direction {
+            Direction::North => self.top,
+            Direction::South => self.bottom,
+            Direction::East => self.right,
+            Direction::West => self.left,
+        }
+    }
+}
+
 impl Default for Walls {
     fn default() -> Self {
         Self {
@@ -84,12 +198,15 @@ impl Default for Walls {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Cell {
     pub x: usize,
     pub y: usize,
     pub visited: bool,
     pub walls: Walls,
+    /// masked cells are not part of the maze
+    #[serde(default)]
+    pub masked: bool,
 }
 
 impl Cell {
@@ -99,6 +216,7 @@ impl Cell {
             y,
             visited: false,
             walls: Walls::default(),
+            masked: false,
         }
     }
 
@@ -120,41 +238,184 @@ impl Cell {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Board {
     pub cells: Vec<Cell>,
     pub path: Vec<usize>,
-    pub board_size: usize,
+    pub width: usize,
+    pub height: usize,
+    pub start: usize,
+    pub goal: usize,
     pub finish: bool,
     pub cell_size: usize,
     pub x: usize,
     pub y: usize,
+    #[serde(default)]
+    pub wrap: Wrap,
 }
 
 impl Board {
-    pub fn new(border: usize, board_size: usize, cell_size: usize) -> Self {
+    pub fn new(border: usize, width: usize, height: usize, cell_size: usize) -> Self {
+        Self::with_wrap(border, width, height, cell_size, Wrap::None)
+    }
+
+    /**
+     * A board whose edges wrap around. An edge only wraps when the board is
+     * at least 3 cells across, otherwise the cells on both sides would
+     * already be neighbors.
+     */
+    pub fn with_wrap(
+        border: usize,
+        width: usize,
+        height: usize,
+        cell_size: usize,
+        wrap: Wrap,
+    ) -> Self {
         let mut board = Self {
             cells: Vec::new(),
             path: vec![0],
-            board_size,
+            width,
+            height,
+            start: 0,
+            goal: width * height - 1,
             finish: false,
             cell_size,
             x: border,
             y: border,
+            wrap,
         };
         board.init();
         board
     }
 
     fn init(&mut self) {
-        for i in 0..self.board_size {
-            for j in 0..self.board_size {
+        for i in 0..self.width {
+            for j in 0..self.height {
                 self.cells.push(Cell::new(i, j));
             }
         }
         // self.cells[0].visited = true;
-        self.cells[0].walls.left = false;
-        self.cells.last_mut().unwrap().walls.right = false;
+        self.set_opening(self.start, false);
+        self.set_opening(self.goal, false);
+    }
+
+    /**
+     * whether the left and right edge meet
+     */
+    pub fn wraps_x(&self) -> bool {
+        self.wrap != Wrap::None && self.width > 2
+    }
+
+    /**
+     * whether the top and bottom edge meet
+     */
+    pub fn wraps_y(&self) -> bool {
+        self.wrap == Wrap::Torus && self.height > 2
+    }
+
+    /**
+     * the wall of a cell that faces outside of the maze, towards the edge
+     * of the board or a masked cell. Interior cells have none.
+     */
+    fn outer_wall(&mut self, index: usize) -> Option<&mut bool> {
+        let neighbors = self.neighbors(index);
+        // prefer the left and right side over the top and bottom
+        let side = [2, 3, 0, 1]
+            .into_iter()
+            .find(|&side| neighbors[side].is_none())?;
+        let walls = &mut self.cells[index].walls;
+        Some(match side {
+            0 => &mut walls.top,
+            1 => &mut walls.bottom,
+            2 => &mut walls.left,
+            _ => &mut walls.right,
+        })
+    }
+
+    fn set_opening(&mut self, index: usize, wall: bool) {
+        if let Some(outer) = self.outer_wall(index) {
+            *outer = wall;
+        }
+    }
+
+    /**
+     * A board in the shape of the mask. The masked cells are left out, the
+     * entrance and exit are moved to the first and last enabled cell. The
+     * enabled cells have to be connected.
+     */
+    pub fn with_mask(border: usize, mask: &Mask, cell_size: usize) -> Result<Self, String> {
+        let mut board = Self::new(border, mask.width, mask.height, cell_size);
+        board.set_opening(board.start, true);
+        board.set_opening(board.goal, true);
+        for cell in &mut board.cells {
+            cell.masked = !mask.is_enabled(cell.x, cell.y);
+        }
+
+        let enabled: Vec<usize> = (0..board.cells.len())
+            .filter(|&index| !board.cells[index].masked)
+            .collect();
+        let (Some(&first), Some(&last)) = (enabled.first(), enabled.last()) else {
+            return Err(String::from("the mask has no enabled cells"));
+        };
+        let mut reached = vec![false; board.cells.len()];
+        reached[first] = true;
+        let mut stack = vec![first];
+        let mut count = 1;
+        while let Some(index) = stack.pop() {
+            for neighbor in board.neighbors(index).into_iter().flatten() {
+                if !reached[neighbor] {
+                    reached[neighbor] = true;
+                    count += 1;
+                    stack.push(neighbor);
+                }
+            }
+        }
+        if count != enabled.len() {
+            return Err(String::from(
+                "the enabled cells of the mask are not connected",
+            ));
+        }
+
+        board.start = first;
+        board.goal = last;
+        board.set_opening(first, false);
+        board.set_opening(last, false);
+        Ok(board)
+    }
+
+    /**
+     * move the entrance to another cell, border cells get an opening
+     */
+    pub fn set_start(&mut self, index: usize) {
+        if self.start != self.goal {
+            self.set_opening(self.start, true);
+        }
+        self.start = index;
+        self.set_opening(index, false);
+    }
+
+    /**
+     * move the exit to another cell, border cells get an opening
+     */
+    pub fn set_goal(&mut self, index: usize) {
+        if self.start != self.goal {
+            self.set_opening(self.goal, true);
+        }
+        self.goal = index;
+        self.set_opening(index, false);
+    }
+
+    /**
+     * the enabled cell at the screen position, if any
+     */
+    pub fn cell_at(&self, x: f32, y: f32) -> Option<usize> {
+        let x = (x - self.x as f32) / self.cell_size as f32;
+        let y = (y - self.y as f32) / self.cell_size as f32;
+        if x < 0.0 || y < 0.0 || x >= self.width as f32 || y >= self.height as f32 {
+            return None;
+        }
+        let index = self.get_index(x as usize, y as usize);
+        (!self.cells[index].masked).then_some(index)
     }
 
     pub fn get_cell(&mut self, index: usize) -> &mut Cell {
@@ -162,41 +423,117 @@ impl Board {
     }
 
     pub fn get_index(&self, x: usize, y: usize) -> usize {
-        let index = x * self.board_size + y;
+        let index = x * self.height + y;
         assert!(self.cells[index].x == x && self.cells[index].y == y,);
         index
     }
 
     /**
-     * return the neighbors [top, bottom, right, left]
+     * return the neighbors [top, bottom, left, right], across the wrapped
+     * edges. Masked cells have no neighbors and are nobody's neighbor.
      */
     pub fn neighbors(&self, cell_index: usize) -> Vec<Option<usize>> {
         let mut res = Vec::<Option<usize>>::new();
-        if self.cells[cell_index].y > 0 {
+        let cell = &self.cells[cell_index];
+        if cell.masked {
+            return vec![None; 4];
+        }
+        if cell.y > 0 {
             res.push(Some(cell_index - 1));
+        } else if self.wraps_y() {
+            res.push(Some(cell_index + self.height - 1));
         } else {
             res.push(None);
         }
-        if self.cells[cell_index].y < self.board_size - 1 {
+        if cell.y < self.height - 1 {
             res.push(Some(cell_index + 1));
+        } else if self.wraps_y() {
+            res.push(Some(cell_index + 1 - self.height));
         } else {
             res.push(None);
         }
-        if self.cells[cell_index].x > 0 {
-            res.push(Some(cell_index - self.board_size));
+        if cell.x > 0 {
+            res.push(Some(cell_index - self.height));
+        } else if self.wraps_x() {
+            res.push(Some(self.get_index(self.width - 1, cell.y)));
         } else {
             res.push(None);
         }
-        if self.cells[cell_index].x < self.board_size - 1 {
-            res.push(Some(cell_index + self.board_size));
+        if cell.x < self.width - 1 {
+            res.push(Some(cell_index + self.height));
+        } else if self.wraps_x() {
+            res.push(Some(self.get_index(0, cell.y)));
         } else {
             res.push(None);
         }
-        res
+        res.into_iter()
+            .map(|neighbor| neighbor.filter(|&neighbor| !self.cells[neighbor].masked))
+            .collect()
+    }
+
+    /**
+     * the side of `cell` that faces `neighbor`, also across the wrapped
+     * edges
+     */
+    pub fn side(&self, cell: usize, neighbor: usize) -> Direction {
+        let side = self
+            .neighbors(cell)
+            .iter()
+            .position(|&other| other == Some(neighbor))
+            .expect("cells are not neighbors");
+        [
+            Direction::North,
+            Direction::South,
+            Direction::West,
+            Direction::East,
+        ][side]
+    }
+
+    /**
+     * the horizontal and vertical distance between two cells, taking the
+     * shorter way around the wrapped edges
+     */
+    pub fn offset(&self, from: usize, to: usize) -> (usize, usize) {
+        let (from, to) = (&self.cells[from], &self.cells[to]);
+        let mut dx = from.x.abs_diff(to.x);
+        let mut dy = from.y.abs_diff(to.y);
+        if self.wraps_x() {
+            dx = dx.min(self.width - dx);
+        }
+        if self.wraps_y() {
+            dy = dy.min(self.height - dy);
+        }
+        (dx, dy)
+    }
+
+    /**
+     * the neighbor on the given side, if it is inside the board
+     */
+    pub fn neighbor(&self, cell_index: usize, direction: Direction) -> Option<usize> {
+        let side = match direction {
+            Direction::North => 0,
+            Direction::South => 1,
+            Direction::West => 2,
+            Direction::East => 3,
+        };
+        self.neighbors(cell_index)[side]
+    }
+
+    /**
+     * the neighbors that can be reached without crossing a wall
+     */
+    pub fn passages(&self, cell_index: usize) -> Vec<usize> {
+        let walls = &self.cells[cell_index].walls;
+        let open = [!walls.top, !walls.bottom, !walls.left, !walls.right];
+        self.neighbors(cell_index)
+            .into_iter()
+            .zip(open)
+            .filter_map(|(neighbor, open)| neighbor.filter(|_| open))
+            .collect()
     }
 
     pub fn remove_wall(&mut self, cell: usize, neighbor: usize) {
-        match self.cells[cell].direction(&self.cells[neighbor]) {
+        match self.side(cell, neighbor) {
             crate::maze::Direction::North => {
                 self.cells[cell].walls.top = false;
                 self.cells[neighbor].walls.bottom = false;
@@ -218,62 +555,256 @@ impl Board {
         self.cells[neighbor].visited = true;
     }
 
-    pub fn draw(&self) {
-        unsafe {
-            for cell in &self.cells {
-                let x = self.x + cell.x * self.cell_size;
-                let y = self.y + cell.y * self.cell_size;
-                if cell.walls.top {
-                    raylib::DrawLine(
-                        x as i32,
-                        y as i32,
-                        (x + self.cell_size) as i32,
-                        y as i32,
-                        WALL_COLOR,
-                    );
-                }
-                if cell.walls.right {
-                    raylib::DrawLine(
-                        (x + self.cell_size) as i32,
-                        y as i32,
-                        (x + self.cell_size) as i32,
-                        (y + self.cell_size) as i32,
-                        WALL_COLOR,
-                    );
-                }
-                if cell.walls.bottom {
-                    raylib::DrawLine(
-                        (x + self.cell_size) as i32,
-                        (y + self.cell_size) as i32,
-                        x as i32,
-                        (y + self.cell_size) as i32,
-                        WALL_COLOR,
-                    );
-                }
-                if cell.walls.left {
-                    raylib::DrawLine(
-                        x as i32,
-                        (y + self.cell_size) as i32,
-                        x as i32,
-                        y as i32,
-                        WALL_COLOR,
-                    );
-                }
-                if !cell.visited {
-                    raylib::DrawRectangle(
-                        x as i32,
-                        y as i32,
-                        (self.cell_size) as i32,
-                        (self.cell_size) as i32,
-                        raylib::Color {
-                            r: 60,
-                            g: 60,
-                            b: 60,
-                            a: 100,
-                        },
-                    );
+    /**
+     * check that both sides of every shared wall agree
+     */
+    pub fn check_walls(&self) -> Result<(), String> {
+        for (index, cell) in self.cells.iter().enumerate() {
+            let neighbors = self.neighbors(index);
+            if let Some(bottom) = neighbors[1]
+                && cell.walls.bottom != self.cells[bottom].walls.top
+            {
+                return Err(format!(
+                    "wall between ({}, {}) and ({}, {}) does not match",
+                    cell.x, cell.y, self.cells[bottom].x, self.cells[bottom].y
+                ));
+            }
+            if let Some(right) = neighbors[3]
+                && cell.walls.right != self.cells[right].walls.left
+            {
+                return Err(format!(
+                    "wall between ({}, {}) and ({}, {}) does not match",
+                    cell.x, cell.y, self.cells[right].x, self.cells[right].y
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn draw(&self, renderer: &mut dyn Renderer) {
+        self.draw_styled(renderer, &Style::default());
+    }
+
+    pub fn draw_styled(&self, renderer: &mut dyn Renderer, style: &Style) {
+        for cell in self.cells.iter().filter(|cell| !cell.masked) {
+            let x = (self.x + cell.x * self.cell_size) as f32;
+            let y = (self.y + cell.y * self.cell_size) as f32;
+            let size = self.cell_size as f32;
+            let top_left = raylib::Vector2::new(x, y);
+            let top_right = raylib::Vector2::new(x + size, y);
+            let bottom_right = raylib::Vector2::new(x + size, y + size);
+            let bottom_left = raylib::Vector2::new(x, y + size);
+            if cell.walls.top {
+                renderer.line(top_left, top_right, style.wall_width, style.wall_color);
+            }
+            if cell.walls.right {
+                renderer.line(top_right, bottom_right, style.wall_width, style.wall_color);
+            }
+            if cell.walls.bottom {
+                renderer.line(
+                    bottom_right,
+                    bottom_left,
+                    style.wall_width,
+                    style.wall_color,
+                );
+            }
+            if cell.walls.left {
+                renderer.line(bottom_left, top_left, style.wall_width, style.wall_color);
+            }
+            if !cell.visited && style.unvisited_color.a > 0 {
+                renderer.rect(x, y, size, size, style.unvisited_color);
+            }
+            // openings on a wrapped edge continue on the opposite edge
+            let seams = [
+                (
+                    self.wraps_y() && cell.y == 0,
+                    !cell.walls.top,
+                    top_left,
+                    top_right,
+                ),
+                (
+                    self.wraps_y() && cell.y == self.height - 1,
+                    !cell.walls.bottom,
+                    bottom_left,
+                    bottom_right,
+                ),
+                (
+                    self.wraps_x() && cell.x == 0,
+                    !cell.walls.left,
+                    top_left,
+                    bottom_left,
+                ),
+                (
+                    self.wraps_x() && cell.x == self.width - 1,
+                    !cell.walls.right,
+                    top_right,
+                    bottom_right,
+                ),
+            ];
+            for (seam, open, from, to) in seams {
+                if seam && open {
+                    renderer.line(from, to, style.wall_width, style.wrap_color);
                 }
             }
         }
+        for (index, color) in [
+            (self.start, style.start_color),
+            (self.goal, style.goal_color),
+        ] {
+            if color.a == 0 {
+                continue;
+            }
+            let cell = &self.cells[index];
+            renderer.circle(
+                raylib::Vector2::new(
+                    (self.x + cell.x * self.cell_size + self.cell_size / 2) as f32,
+                    (self.y + cell.y * self.cell_size + self.cell_size / 2) as f32,
+                ),
+                self.cell_size as f32 / 4.0,
+                color,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moving_the_entrance_and_exit() {
+        let mut board = Board::new(0, 5, 4, CELL_SIZE);
+        assert!(!board.cells[board.start].walls.left);
+        assert!(!board.cells[board.goal].walls.right);
+
+        let old_start = board.start;
+        board.set_start(board.get_index(2, 0));
+        assert!(board.cells[old_start].walls.left);
+        assert!(!board.cells[board.start].walls.top);
+
+        // an interior cell has no outer wall to open
+        let old_goal = board.goal;
+        board.set_goal(board.get_index(2, 2));
+        assert!(board.cells[old_goal].walls.right);
+        assert_eq!(board.cells[board.goal].walls, Walls::default());
+    }
+
+    #[test]
+    fn solvers_use_the_entrance_and_exit() {
+        let mut board = Board::new(0, 9, 7, CELL_SIZE);
+        let mut generator = generator::by_name("wilson", &mut board, 9).unwrap();
+        headless::run(&mut board, generator.as_mut());
+        board.set_start(board.get_index(4, 0));
+        board.set_goal(board.get_index(3, 4));
+        for name in solver::SOLVERS {
+            let mut solver = solver::by_name(name, &board, 9).unwrap();
+            let stats = headless::solve(&board, solver.as_mut(), 100_000);
+            assert!(stats.solved, "{name}: {:?}", stats.error);
+            // djikstra walks its path back from the goal
+            let path = solver.get_path();
+            let mut ends = [path[0], path[path.len() - 1]];
+            ends.sort();
+            let mut expected = [board.start, board.goal];
+            expected.sort();
+            assert_eq!(ends, expected, "{name}");
+        }
+    }
+
+    #[test]
+    fn cell_at() {
+        let mut mask = Mask::new(4, 3);
+        mask.set(1, 2, false);
+        let board = Board::with_mask(5, &mask, CELL_SIZE).unwrap();
+        assert_eq!(board.cell_at(5.0, 5.0), Some(board.get_index(0, 0)));
+        assert_eq!(board.cell_at(38.0, 24.0), Some(board.get_index(3, 1)));
+        assert_eq!(board.cell_at(16.0, 26.0), None);
+        assert_eq!(board.cell_at(4.0, 5.0), None);
+        assert_eq!(board.cell_at(45.0, 5.0), None);
+    }
+
+    #[test]
+    fn wrapped_neighbors() {
+        let board = Board::with_wrap(0, 5, 4, CELL_SIZE, Wrap::Cylinder);
+        let at = |x, y| Some(board.get_index(x, y));
+        assert_eq!(
+            board.neighbors(board.get_index(0, 2)),
+            [at(0, 1), at(0, 3), at(4, 2), at(1, 2)]
+        );
+        assert_eq!(
+            board.neighbors(board.get_index(4, 0)),
+            [None, at(4, 1), at(3, 0), at(0, 0)]
+        );
+        assert_eq!(
+            board.side(board.get_index(0, 2), board.get_index(4, 2)),
+            Direction::West
+        );
+        assert_eq!(
+            board.offset(board.get_index(0, 0), board.get_index(4, 3)),
+            (1, 3)
+        );
+
+        let board = Board::with_wrap(0, 5, 4, CELL_SIZE, Wrap::Torus);
+        let at = |x, y| Some(board.get_index(x, y));
+        assert_eq!(
+            board.neighbors(board.get_index(4, 0)),
+            [at(4, 3), at(4, 1), at(3, 0), at(0, 0)]
+        );
+        assert_eq!(
+            board.side(board.get_index(2, 3), board.get_index(2, 0)),
+            Direction::South
+        );
+        assert_eq!(
+            board.offset(board.get_index(0, 0), board.get_index(4, 3)),
+            (1, 1)
+        );
+
+        // two cells across are neighbors already, that edge does not wrap
+        let board = Board::with_wrap(0, 2, 2, CELL_SIZE, Wrap::Torus);
+        assert!(!board.wraps_x() && !board.wraps_y());
+        assert_eq!(board.neighbors(0), [None, Some(1), None, Some(2)]);
+    }
+
+    #[test]
+    fn walls_across_the_seam() {
+        let mut board = Board::with_wrap(0, 5, 4, CELL_SIZE, Wrap::Torus);
+        // no edge is left for the entrance and exit
+        assert!(board.cells[board.start].walls.left && board.cells[board.start].walls.top);
+        let (left, right) = (board.get_index(0, 1), board.get_index(4, 1));
+        board.remove_wall(left, right);
+        assert!(!board.cells[left].walls.left && !board.cells[right].walls.right);
+        assert_eq!(board.passages(right), [left]);
+        let (top, bottom) = (board.get_index(2, 0), board.get_index(2, 3));
+        board.remove_wall(bottom, top);
+        assert!(!board.cells[top].walls.top && !board.cells[bottom].walls.bottom);
+        assert_eq!(board.check_walls(), Ok(()));
+
+        // a cylinder keeps the top and bottom edge for the openings
+        let board = Board::with_wrap(0, 5, 4, CELL_SIZE, Wrap::Cylinder);
+        assert!(!board.cells[board.start].walls.top);
+        assert!(!board.cells[board.goal].walls.bottom);
+    }
+
+    #[test]
+    fn shortest_paths_across_the_seam() {
+        let mut board = Board::with_wrap(0, 9, 8, CELL_SIZE, Wrap::Torus);
+        let mut generator = generator::by_name("prim", &mut board, 5).unwrap();
+        headless::run(&mut board, generator.as_mut());
+        braid::braid_with_seed(&mut board, 0.5, 5);
+        board.set_start(board.get_index(0, 0));
+        board.set_goal(board.get_index(8, 7));
+        let length = analysis::distances(&board, board.start)[board.goal].unwrap() + 1;
+        for name in [
+            "djikstra",
+            "a* manhattan",
+            "breadth first search",
+            "bidirectional",
+        ] {
+            let mut solver = solver::by_name(name, &board, 5).unwrap();
+            assert!(
+                headless::solve(&board, solver.as_mut(), 1000).solved,
+                "{name}"
+            );
+            assert_eq!(solver.get_path().len(), length, "{name}");
+        }
     }
 }
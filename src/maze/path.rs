@@ -1,9 +1,14 @@
 use crate::{
-    maze::{Board, Cell, PATH_COLOR},
-    raylib,
+    maze::{
+        Board, Cell, Direction as Side,
+        render::{Renderer, Style},
+    },
+    raylib::Vector2,
 };
 
-#[derive(Debug)]
+/// Shape of the path inside one cell. `Start*` is the last cell of the path
+/// and `End*` the first, named after the side the path leaves through.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Direction {
     UpLeft,
     UpRight,
@@ -21,281 +26,276 @@ pub enum Direction {
     EndRight,
 }
 
-fn path_dot(x: usize, y: usize, cell: &Cell, cell_size: usize) {
-    let half_cell = cell_size / 2;
-    unsafe {
-        raylib::DrawCircle(
-            (x + cell.x * cell_size + half_cell) as i32,
-            (y + cell.y * cell_size + half_cell) as i32,
-            cell_size as f32 / 10.0,
-            PATH_COLOR,
-        );
+impl Direction {
+    /// The shape of a cell the path enters through `prev` and leaves through
+    /// `next`, `None` if both are missing or the same side.
+    pub fn from_sides(prev: Option<Side>, next: Option<Side>) -> Option<Self> {
+        let direction = match (prev, next) {
+            (Some(prev), Some(next)) => match (prev, next) {
+                (Side::North, Side::South) | (Side::South, Side::North) => Direction::Vertical,
+                (Side::West, Side::East) | (Side::East, Side::West) => Direction::Horizontal,
+                (Side::North, Side::West) | (Side::West, Side::North) => Direction::UpLeft,
+                (Side::North, Side::East) | (Side::East, Side::North) => Direction::UpRight,
+                (Side::South, Side::West) | (Side::West, Side::South) => Direction::DownLeft,
+                (Side::South, Side::East) | (Side::East, Side::South) => Direction::DownRight,
+                _ => return None,
+            },
+            (None, Some(next)) => match next {
+                Side::North => Direction::EndUp,
+                Side::South => Direction::EndDown,
+                Side::West => Direction::EndLeft,
+                Side::East => Direction::EndRight,
+            },
+            (Some(prev), None) => match prev {
+                Side::North => Direction::StartUp,
+                Side::South => Direction::StartDown,
+                Side::West => Direction::StartLeft,
+                Side::East => Direction::StartRight,
+            },
+            (None, None) => return None,
+        };
+        Some(direction)
+    }
+
+    /// The sides of the cell the path touches.
+    pub fn sides(self) -> Vec<Side> {
+        match self {
+            Direction::UpLeft => vec![Side::North, Side::West],
+            Direction::UpRight => vec![Side::North, Side::East],
+            Direction::DownLeft => vec![Side::South, Side::West],
+            Direction::DownRight => vec![Side::South, Side::East],
+            Direction::Horizontal => vec![Side::West, Side::East],
+            Direction::Vertical => vec![Side::North, Side::South],
+            Direction::StartLeft | Direction::EndLeft => vec![Side::West],
+            Direction::StartDown | Direction::EndDown => vec![Side::South],
+            Direction::StartUp | Direction::EndUp => vec![Side::North],
+            Direction::StartRight | Direction::EndRight => vec![Side::East],
+        }
+    }
+
+    /// First or last cell of the path, drawn with a dot.
+    pub fn is_end(self) -> bool {
+        self.sides().len() == 1
     }
 }
 
-fn path_down(x: usize, y: usize, cell: &Cell, cell_size: usize) {
+fn center(x: usize, y: usize, cell: &Cell, cell_size: usize) -> Vector2 {
     let half_cell = cell_size / 2;
-    unsafe {
-        raylib::DrawLine(
-            (x + cell.x * cell_size + half_cell) as i32,
-            (y + cell.y * cell_size + half_cell) as i32,
-            (x + cell.x * cell_size + half_cell) as i32,
-            (y + cell.y * cell_size + cell_size) as i32,
-            PATH_COLOR,
-        );
-    }
+    Vector2::new(
+        (x + cell.x * cell_size + half_cell) as f32,
+        (y + cell.y * cell_size + half_cell) as f32,
+    )
+}
+
+fn path_dot(
+    x: usize,
+    y: usize,
+    cell: &Cell,
+    cell_size: usize,
+    style: &Style,
+    renderer: &mut dyn Renderer,
+) {
+    renderer.circle(
+        center(x, y, cell, cell_size),
+        cell_size as f32 / 10.0,
+        style.path_color,
+    );
 }
-fn path_up(x: usize, y: usize, cell: &Cell, cell_size: usize) {
+
+fn path_down(
+    x: usize,
+    y: usize,
+    cell: &Cell,
+    cell_size: usize,
+    style: &Style,
+    renderer: &mut dyn Renderer,
+) {
     let half_cell = cell_size / 2;
-    unsafe {
-        raylib::DrawLine(
-            (x + cell.x * cell_size + half_cell) as i32,
-            (y + cell.y * cell_size + half_cell) as i32,
-            (x + cell.x * cell_size + half_cell) as i32,
-            (y + cell.y * cell_size) as i32,
-            PATH_COLOR,
-        );
-    }
+    renderer.line(
+        center(x, y, cell, cell_size),
+        Vector2::new(
+            (x + cell.x * cell_size + half_cell) as f32,
+            (y + cell.y * cell_size + cell_size) as f32,
+        ),
+        style.path_width,
+        style.path_color,
+    );
+}
+fn path_up(
+    x: usize,
+    y: usize,
+    cell: &Cell,
+    cell_size: usize,
+    style: &Style,
+    renderer: &mut dyn Renderer,
+) {
+    let half_cell = cell_size / 2;
+    renderer.line(
+        center(x, y, cell, cell_size),
+        Vector2::new(
+            (x + cell.x * cell_size + half_cell) as f32,
+            (y + cell.y * cell_size) as f32,
+        ),
+        style.path_width,
+        style.path_color,
+    );
 }
 
-fn path_left(x: usize, y: usize, cell: &Cell, cell_size: usize) {
+fn path_left(
+    x: usize,
+    y: usize,
+    cell: &Cell,
+    cell_size: usize,
+    style: &Style,
+    renderer: &mut dyn Renderer,
+) {
     let half_cell = cell_size / 2;
-    unsafe {
-        raylib::DrawLine(
-            (x + cell.x * cell_size + half_cell) as i32,
-            (y + cell.y * cell_size + half_cell) as i32,
-            (x + cell.x * cell_size) as i32,
-            (y + cell.y * cell_size + half_cell) as i32,
-            PATH_COLOR,
-        );
-    }
+    renderer.line(
+        center(x, y, cell, cell_size),
+        Vector2::new(
+            (x + cell.x * cell_size) as f32,
+            (y + cell.y * cell_size + half_cell) as f32,
+        ),
+        style.path_width,
+        style.path_color,
+    );
 }
 
-fn path_right(x: usize, y: usize, cell: &Cell, cell_size: usize) {
+fn path_right(
+    x: usize,
+    y: usize,
+    cell: &Cell,
+    cell_size: usize,
+    style: &Style,
+    renderer: &mut dyn Renderer,
+) {
     let half_cell = cell_size / 2;
-    unsafe {
-        raylib::DrawLine(
-            (x + cell.x * cell_size + half_cell) as i32,
-            (y + cell.y * cell_size + half_cell) as i32,
-            (x + cell.x * cell_size + cell_size) as i32,
-            (y + cell.y * cell_size + half_cell) as i32,
-            PATH_COLOR,
-        );
+    renderer.line(
+        center(x, y, cell, cell_size),
+        Vector2::new(
+            (x + cell.x * cell_size + cell_size) as f32,
+            (y + cell.y * cell_size + half_cell) as f32,
+        ),
+        style.path_width,
+        style.path_color,
+    );
+}
+
+/// The side of `current` that `other` lies on, by their coordinates.
+fn side(current: &Cell, other: &Cell) -> Option<Side> {
+    match (
+        other.x as isize - current.x as isize,
+        other.y as isize - current.y as isize,
+    ) {
+        (0, -1) => Some(Side::North),
+        (0, 1) => Some(Side::South),
+        (-1, 0) => Some(Side::West),
+        (1, 0) => Some(Side::East),
+        _ => None,
     }
 }
 
-fn direction(current: &Cell, prev: Option<&Cell>, next: Option<&Cell>) -> Direction {
-    // +---+---+---+    current.y < previous.y &&
-    // +   +   +   +    current.y == next.y &&
-    // +---+---+---+    current.x == previous.x &&
-    // +   + c + n +    current.x < next.x
-    // +---+---+---+
-    // +   + p +   +
-    // +---+---+---+
-    // +---+---+---+    current.y == previous.y &&
-    // +   +   +   +    current.y < next.y &&
-    // +---+---+---+    current.x < previous.x &&
-    // +   + c + p +    currnet.x == next.x
-    // +---+---+---+
-    // +   + n +   +
-    // +---+---+---+
-    //
-    // +---+---+---+    current.y < previous.y &&
-    // +   +   +   +    current.y == next.y &&
-    // +---+---+---+    currnet.x == previous.x &&
-    // + n + c +   +    current.x > next.x
-    // +---+---+---+
-    // +   + p +   +
-    // +---+---+---+
-    // +---+---+---+    current.y == previous.y &&
-    // +   +   +   +    current.y < next.y &&
-    // +---+---+---+    current.x > previous.x &&
-    // + p + c +   +    current.x == next.x
-    // +---+---+---+
-    // +   + n +   +
-    // +---+---+---+
+/**
+ * Shape of the path in `current` between the cells before and after it.
+ * Only looks at the coordinates, so a step over a wrapped edge is not
+ * found, `segment` follows the board's neighbors instead.
+ */
+pub fn direction(current: &Cell, prev: Option<&Cell>, next: Option<&Cell>) -> Direction {
+    Direction::from_sides(
+        prev.and_then(|prev| side(current, prev)),
+        next.and_then(|next| side(current, next)),
+    )
+    .expect("direction not found")
+}
 
-    //
-    // +---+---+---+    current.y > previous.y &&
-    // +   + p +   +    current.y == next.y &&
-    // +---+---+---+    current.x == previous.x &&
-    // +   + c + n +    currnet.x < next.x
-    // +---+---+---+
-    // +   +   +   +
-    // +---+---+---+
-    // +---+---+---+    current.y == previous.y &&
-    // +   + n +   +    current.y > next.y &&
-    // +---+---+---+    current.x < prvious.x &&
-    // +   + c + p +    current.x == next.x
-    // +---+---+---+
-    // +   +   +   +
-    // +---+---+---+
-    //
-    // +---+---+---+    current.y > previous.y &&
-    // +   + p +   +    current.y == next.y &&
-    // +---+---+---+    current.x == previous.x &&
-    // + n + c +   +    current.x > next.x
-    // +---+---+---+
-    // +   +   +   +
-    // +---+---+---+
-    // +---+---+---+    current.y == previous.y &&
-    // +   + n +   +    current.y > next.y &&
-    // +---+---+---+    current.x > previous.x &&
-    // + p + c +   +    current.x == next.x
-    // +---+---+---+
-    // +   +   +   +
-    // +---+---+---+
-    //
-    if let (Some(prev), Some(next)) = (prev, next) {
-        if current.x == next.x && current.x == prev.x {
-            return Direction::Vertical;
-        } else if current.y == next.y && current.y == prev.y {
-            return Direction::Horizontal;
-        } else if (current.y < prev.y
-            && current.y == next.y
-            && current.x == prev.x
-            && current.x < next.x)
-            || (current.y == prev.y
-                && current.y < next.y
-                && current.x < prev.x
-                && current.x == next.x)
-        {
-            return Direction::DownRight;
-        } else if (current.y < prev.y
-            && current.y == next.y
-            && current.x == prev.x
-            && current.x > next.x)
-            || (current.y == prev.y
-                && current.y < next.y
-                && current.x > prev.x
-                && current.x == next.x)
-        {
-            return Direction::DownLeft;
-        } else if (current.y > prev.y
-            && current.y == next.y
-            && current.x == prev.x
-            && current.x < next.x)
-            || (current.y == prev.y
-                && current.y > next.y
-                && current.x < prev.x
-                && current.x == next.x)
-        {
-            return Direction::UpRight;
-        } else if (current.y > prev.y
-            && current.y == next.y
-            && current.x == prev.x
-            && current.x > next.x)
-            || (current.y == prev.y
-                && current.y > next.y
-                && current.x > prev.x
-                && current.x == next.x)
-        {
-            return Direction::UpLeft;
-        }
-    } else if let Some(next) = next {
-        if next.x > current.x {
-            return Direction::EndRight;
-        } else if next.x < current.x {
-            return Direction::EndLeft;
-        } else if next.y > current.y {
-            return Direction::EndDown;
-        } else if next.y < current.y {
-            return Direction::EndUp;
-        } else {
-            panic!("direction not found")
-        }
-    } else if let Some(prev) = prev {
-        if prev.x > current.x {
-            return Direction::StartRight;
-        } else if prev.x < current.x {
-            return Direction::StartLeft;
-        } else if prev.y > current.y {
-            return Direction::StartDown;
-        } else if prev.y < current.y {
-            return Direction::StartUp;
-        } else {
-            panic!("direction not found")
-        }
-    }
-    panic!("direction not found")
+/// Shape of the path in its cell at `index`, also across the wrapped edges
+/// of `board`. `None` for a path of a single cell.
+pub fn segment(board: &Board, path: &[usize], index: usize) -> Option<Direction> {
+    let cell = path[index];
+    let prev = index
+        .checked_sub(1)
+        .map(|prev| board.side(cell, path[prev]));
+    let next = path.get(index + 1).map(|&next| board.side(cell, next));
+    Direction::from_sides(prev, next)
 }
 
-pub fn draw_path(board: &Board, path: &[usize]) {
-    if path.len() == 1 {
-        return;
-    }
-    // draw the path
+pub fn draw_path(board: &Board, path: &[usize], renderer: &mut dyn Renderer) {
+    draw_path_styled(board, path, &Style::default(), renderer);
+}
+
+pub fn draw_path_styled(board: &Board, path: &[usize], style: &Style, renderer: &mut dyn Renderer) {
+    // every cell gets a line from its center to the sides the path passes,
+    // the window, svg and png all draw through here
     let x = board.x;
     let y = board.y;
-    unsafe {
-        for (i, item) in path.iter().enumerate() {
-            let prev = if i > 0 { path.get(i - 1) } else { None };
-            let next = path.get(i + 1); // get handles out-of-bounds by returning None
-            let direction = direction(
-                &board.cells[*item],
-                if let Some(prev) = prev {
-                    Some(&board.cells[*prev])
-                } else {
-                    None
-                },
-                if let Some(next) = next {
-                    Some(&board.cells[*next])
-                } else {
-                    None
-                },
-            );
-            match direction {
-                Direction::Horizontal => {
-                    raylib::DrawLine(
-                        (x + board.cells[*item].x * board.cell_size) as i32,
-                        (y + board.cells[*item].y * board.cell_size + board.cell_size / 2) as i32,
-                        (x + board.cells[*item].x * board.cell_size + board.cell_size) as i32,
-                        (y + board.cells[*item].y * board.cell_size + board.cell_size / 2) as i32,
-                        PATH_COLOR,
-                    );
-                }
-                Direction::Vertical => {
-                    raylib::DrawLine(
-                        (x + board.cells[*item].x * board.cell_size + board.cell_size / 2) as i32,
-                        (y + board.cells[*item].y * board.cell_size) as i32,
-                        (x + board.cells[*item].x * board.cell_size + board.cell_size / 2) as i32,
-                        (y + board.cells[*item].y * board.cell_size + board.cell_size) as i32,
-                        PATH_COLOR,
-                    );
-                }
-                Direction::StartLeft | Direction::EndLeft => {
-                    path_dot(x, y, &board.cells[*item], board.cell_size);
-                    path_left(x, y, &board.cells[*item], board.cell_size);
-                }
-                Direction::StartRight | Direction::EndRight => {
-                    path_dot(x, y, &board.cells[*item], board.cell_size);
-                    path_right(x, y, &board.cells[*item], board.cell_size);
-                }
-                Direction::StartUp | Direction::EndUp => {
-                    path_dot(x, y, &board.cells[*item], board.cell_size);
-                    path_up(x, y, &board.cells[*item], board.cell_size);
-                }
-                Direction::StartDown | Direction::EndDown => {
-                    path_dot(x, y, &board.cells[*item], board.cell_size);
-                    path_down(x, y, &board.cells[*item], board.cell_size);
-                }
-                Direction::UpLeft => {
-                    path_up(x, y, &board.cells[*item], board.cell_size);
-                    path_left(x, y, &board.cells[*item], board.cell_size);
-                }
-                Direction::UpRight => {
-                    path_up(x, y, &board.cells[*item], board.cell_size);
-                    path_right(x, y, &board.cells[*item], board.cell_size);
-                }
-                Direction::DownLeft => {
-                    path_down(x, y, &board.cells[*item], board.cell_size);
-                    path_left(x, y, &board.cells[*item], board.cell_size);
-                }
-                Direction::DownRight => {
-                    path_down(x, y, &board.cells[*item], board.cell_size);
-                    path_right(x, y, &board.cells[*item], board.cell_size);
-                }
+    let size = board.cell_size;
+    for (index, item) in path.iter().enumerate() {
+        let Some(direction) = segment(board, path, index) else {
+            continue;
+        };
+        let cell = &board.cells[*item];
+        if direction.is_end() {
+            path_dot(x, y, cell, size, style, renderer);
+        }
+        for side in direction.sides() {
+            match side {
+                Side::North => path_up(x, y, cell, size, style, renderer),
+                Side::South => path_down(x, y, cell, size, style, renderer),
+                Side::West => path_left(x, y, cell, size, style, renderer),
+                Side::East => path_right(x, y, cell, size, style, renderer),
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::{CELL_SIZE, Wrap};
+
+    #[test]
+    fn shapes() {
+        let board = Board::new(0, 3, 3, CELL_SIZE);
+        let cell = |x, y| &board.cells[board.get_index(x, y)];
+        let center = cell(1, 1);
+        assert_eq!(
+            direction(center, Some(cell(1, 0)), Some(cell(1, 2))),
+            Direction::Vertical
+        );
+        assert_eq!(
+            direction(center, Some(cell(0, 1)), Some(cell(2, 1))),
+            Direction::Horizontal
+        );
+        assert_eq!(
+            direction(center, Some(cell(1, 2)), Some(cell(2, 1))),
+            Direction::DownRight
+        );
+        assert_eq!(
+            direction(center, Some(cell(2, 1)), Some(cell(1, 0))),
+            Direction::UpRight
+        );
+        assert_eq!(
+            direction(center, None, Some(cell(0, 1))),
+            Direction::EndLeft
+        );
+        assert_eq!(
+            direction(center, Some(cell(1, 2)), None),
+            Direction::StartDown
+        );
+    }
+
+    #[test]
+    fn segments_cross_wrapped_edges() {
+        let board = Board::with_wrap(0, 4, 3, CELL_SIZE, Wrap::Cylinder);
+        // from the last column over the seam into the first one and down
+        let path = [
+            board.get_index(3, 0),
+            board.get_index(0, 0),
+            board.get_index(0, 1),
+        ];
+        assert_eq!(segment(&board, &path, 0), Some(Direction::EndRight));
+        assert_eq!(segment(&board, &path, 1), Some(Direction::DownLeft));
+        assert_eq!(segment(&board, &path, 2), Some(Direction::StartUp));
+        assert_eq!(segment(&board, &path[..1], 0), None);
+    }
+}
@@ -0,0 +1,397 @@
+use std::{f32::consts::TAU, ops::Range};
+
+use crate::{
+    maze::{
+        render::{Renderer, Style},
+        topology::Topology,
+    },
+    raylib::{Rectangle, Vector2},
+};
+
+#[derive(Clone, Debug)]
+pub struct PolarCell {
+    pub ring: usize,
+    /// position in the ring, counter clockwise starting at the positive x
+    /// axis
+    pub position: usize,
+    pub visited: bool,
+    /// wall on the arc towards the center
+    pub inner: bool,
+    /// wall between this cell and the next one counter clockwise
+    pub ccw: bool,
+}
+
+impl PolarCell {
+    pub fn new(ring: usize, position: usize) -> Self {
+        Self {
+            ring,
+            position,
+            visited: false,
+            inner: true,
+            ccw: true,
+        }
+    }
+}
+
+/// A circular grid. Ring 0 is a single cell in the center, every other ring
+/// is split into cells about as wide as they are high, so the outer rings
+/// hold more cells. A cell has its inner and counter clockwise wall, the
+/// outer walls belong to the next ring. The maze is entered from the outer
+/// ring and ends in the center.
+#[derive(Clone, Debug)]
+pub struct PolarBoard {
+    pub cells: Vec<PolarCell>,
+    /// index of the first cell of every ring
+    pub rings: Vec<usize>,
+    pub start: usize,
+    pub goal: usize,
+    /// height of a ring
+    pub cell_size: usize,
+    pub x: usize,
+    pub y: usize,
+}
+
+impl PolarBoard {
+    pub fn new(border: usize, rings: usize, cell_size: usize) -> Self {
+        let mut cells = vec![PolarCell::new(0, 0)];
+        let mut offsets = vec![0];
+        let mut previous = 1;
+        for ring in 1..rings {
+            // split the cells of the previous ring until they are about
+            // square again
+            let width = TAU * ring as f32 / previous as f32;
+            let count = previous * (width.round() as usize).max(1);
+            offsets.push(cells.len());
+            cells.extend((0..count).map(|position| PolarCell::new(ring, position)));
+            previous = count;
+        }
+        Self {
+            start: cells.len() - previous,
+            goal: 0,
+            cells,
+            rings: offsets,
+            cell_size,
+            x: border,
+            y: border,
+        }
+    }
+
+    pub fn ring_len(&self, ring: usize) -> usize {
+        let end = self
+            .rings
+            .get(ring + 1)
+            .copied()
+            .unwrap_or(self.cells.len());
+        end - self.rings[ring]
+    }
+
+    /// Index of the cell at `position` in `ring`, the position wraps around.
+    pub fn get_index(&self, ring: usize, position: usize) -> usize {
+        self.rings[ring] + position % self.ring_len(ring)
+    }
+
+    /// The neighbor in the same ring, counter clockwise.
+    pub fn next(&self, index: usize) -> Option<usize> {
+        let cell = &self.cells[index];
+        (cell.ring > 0).then(|| self.get_index(cell.ring, cell.position + 1))
+    }
+
+    /// The neighbor in the same ring, clockwise.
+    pub fn previous(&self, index: usize) -> Option<usize> {
+        let cell = &self.cells[index];
+        (cell.ring > 0)
+            .then(|| self.get_index(cell.ring, cell.position + self.ring_len(cell.ring) - 1))
+    }
+
+    /// The neighbor in the next ring towards the center.
+    pub fn parent(&self, index: usize) -> Option<usize> {
+        let cell = &self.cells[index];
+        (cell.ring > 0).then(|| {
+            let ratio = self.ring_len(cell.ring) / self.ring_len(cell.ring - 1);
+            self.get_index(cell.ring - 1, cell.position / ratio)
+        })
+    }
+
+    /// The neighbors in the next ring away from the center.
+    pub fn children(&self, index: usize) -> Range<usize> {
+        let cell = &self.cells[index];
+        if cell.ring + 1 >= self.rings.len() {
+            return 0..0;
+        }
+        let ratio = self.ring_len(cell.ring + 1) / self.ring_len(cell.ring);
+        let first = self.rings[cell.ring + 1] + cell.position * ratio;
+        first..first + ratio
+    }
+
+    fn origin(&self) -> Vector2 {
+        let radius = (self.rings.len() * self.cell_size) as f32;
+        Vector2::new(self.x as f32 + radius, self.y as f32 + radius)
+    }
+
+    fn point(&self, radius: f32, angle: f32) -> Vector2 {
+        let origin = self.origin();
+        Vector2::new(
+            origin.x + radius * angle.cos(),
+            origin.y - radius * angle.sin(),
+        )
+    }
+
+    /// Angles of the sides of the cell.
+    fn angles(&self, cell: &PolarCell) -> (f32, f32) {
+        let theta = TAU / self.ring_len(cell.ring) as f32;
+        (
+            cell.position as f32 * theta,
+            (cell.position + 1) as f32 * theta,
+        )
+    }
+}
+
+impl Topology for PolarBoard {
+    fn cell_count(&self) -> usize {
+        self.cells.len()
+    }
+
+    fn adjacent(&self, cell: usize) -> Vec<usize> {
+        let mut adjacent: Vec<usize> = [self.next(cell), self.previous(cell), self.parent(cell)]
+            .into_iter()
+            .flatten()
+            .chain(self.children(cell))
+            .collect();
+        adjacent.dedup();
+        adjacent
+    }
+
+    fn passages(&self, cell: usize) -> Vec<usize> {
+        let mut passages = Vec::new();
+        if let Some(next) = self.next(cell)
+            && !self.cells[cell].ccw
+        {
+            passages.push(next);
+        }
+        if let Some(previous) = self.previous(cell)
+            && !self.cells[previous].ccw
+        {
+            passages.push(previous);
+        }
+        if let Some(parent) = self.parent(cell)
+            && !self.cells[cell].inner
+        {
+            passages.push(parent);
+        }
+        passages.extend(
+            self.children(cell)
+                .filter(|&child| !self.cells[child].inner),
+        );
+        passages.dedup();
+        passages
+    }
+
+    fn remove_wall(&mut self, cell: usize, neighbor: usize) {
+        if self.next(cell) == Some(neighbor) {
+            self.cells[cell].ccw = false;
+        } else if self.previous(cell) == Some(neighbor) {
+            self.cells[neighbor].ccw = false;
+        } else if self.parent(cell) == Some(neighbor) {
+            self.cells[cell].inner = false;
+        } else if self.parent(neighbor) == Some(cell) {
+            self.cells[neighbor].inner = false;
+        } else {
+            panic!("cells are not neighbors");
+        }
+        self.cells[cell].visited = true;
+        self.cells[neighbor].visited = true;
+    }
+
+    fn visited(&self, cell: usize) -> bool {
+        self.cells[cell].visited
+    }
+
+    fn set_visited(&mut self, cell: usize) {
+        self.cells[cell].visited = true;
+    }
+
+    fn start(&self) -> usize {
+        self.start
+    }
+
+    fn goal(&self) -> usize {
+        self.goal
+    }
+
+    fn center(&self, cell: usize) -> Vector2 {
+        let cell = &self.cells[cell];
+        if cell.ring == 0 {
+            return self.origin();
+        }
+        let (start, end) = self.angles(cell);
+        self.point(
+            (cell.ring as f32 + 0.5) * self.cell_size as f32,
+            (start + end) / 2.0,
+        )
+    }
+
+    fn cell_size(&self) -> f32 {
+        self.cell_size as f32
+    }
+
+    fn bounds(&self) -> Rectangle {
+        let size = (2 * self.rings.len() * self.cell_size) as f32;
+        Rectangle::new(self.x as f32, self.y as f32, size, size)
+    }
+
+    fn draw_board(&self, style: &Style, renderer: &mut dyn Renderer) {
+        let size = self.cell_size as f32;
+        let origin = self.origin();
+        for cell in &self.cells {
+            let inner = cell.ring as f32 * size;
+            let (start, end) = self.angles(cell);
+            if !cell.visited && style.unvisited_color.a > 0 {
+                if cell.ring == 0 {
+                    renderer.circle(origin, size, style.unvisited_color);
+                } else {
+                    // fill the ring segment with thin quads
+                    let outer = inner + size;
+                    let parts = ((end - start) * outer / 8.0).ceil().max(1.0) as usize;
+                    for part in 0..parts {
+                        let a = start + (end - start) * part as f32 / parts as f32;
+                        let b = start + (end - start) * (part + 1) as f32 / parts as f32;
+                        let (inner_a, inner_b) = (self.point(inner, a), self.point(inner, b));
+                        let (outer_a, outer_b) = (self.point(outer, a), self.point(outer, b));
+                        renderer.triangle(inner_a, outer_a, outer_b, style.unvisited_color);
+                        renderer.triangle(inner_a, outer_b, inner_b, style.unvisited_color);
+                    }
+                }
+            }
+            if cell.ring == 0 {
+                continue;
+            }
+            if cell.inner {
+                renderer.arc(
+                    origin,
+                    inner,
+                    start,
+                    end,
+                    style.wall_width,
+                    style.wall_color,
+                );
+            }
+            if cell.ccw {
+                renderer.line(
+                    self.point(inner, end),
+                    self.point(inner + size, end),
+                    style.wall_width,
+                    style.wall_color,
+                );
+            }
+        }
+
+        // the outer wall, open at the start
+        let ring = self.rings.len() - 1;
+        let radius = self.rings.len() as f32 * size;
+        for index in self.rings[ring]..self.cells.len() {
+            if index != self.start {
+                let (start, end) = self.angles(&self.cells[index]);
+                renderer.arc(
+                    origin,
+                    radius,
+                    start,
+                    end,
+                    style.wall_width,
+                    style.wall_color,
+                );
+            }
+        }
+
+        for (index, color) in [
+            (self.start, style.start_color),
+            (self.goal, style.goal_color),
+        ] {
+            renderer.circle(self.center(index), size / 4.0, color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::{analysis, generator, headless, solver::bfs::Bfs};
+
+    #[test]
+    fn rings_are_subdivided() {
+        let board = PolarBoard::new(0, 6, 10);
+        let lengths: Vec<usize> = (0..6).map(|ring| board.ring_len(ring)).collect();
+        assert_eq!(lengths, [1, 6, 12, 24, 24, 24]);
+        assert_eq!(board.start, board.rings[5]);
+        assert_eq!(board.goal, 0);
+
+        // the center has no ring neighbors, all of the first ring outside
+        assert_eq!(
+            (board.next(0), board.previous(0), board.parent(0)),
+            (None, None, None)
+        );
+        assert_eq!(board.children(0), 1..7);
+        // a ring twice as long splits every cell in two
+        let cell = board.get_index(1, 2);
+        assert_eq!(
+            board.children(cell),
+            board.get_index(2, 4)..board.get_index(2, 6)
+        );
+        assert_eq!(board.parent(board.get_index(2, 5)), Some(cell));
+        // a ring just as long has one cell above another
+        let cell = board.get_index(3, 7);
+        assert_eq!(
+            board.children(cell),
+            board.get_index(4, 7)..board.get_index(4, 8)
+        );
+        assert_eq!(board.parent(board.get_index(4, 7)), Some(cell));
+        assert!(board.children(board.get_index(5, 0)).is_empty());
+    }
+
+    #[test]
+    fn rings_wrap_around() {
+        let board = PolarBoard::new(0, 4, 10);
+        let first = board.get_index(2, 0);
+        let last = board.get_index(2, 11);
+        assert_eq!(board.get_index(2, 12), first);
+        assert_eq!(board.next(last), Some(first));
+        assert_eq!(board.previous(first), Some(last));
+        for cell in 0..board.cells.len() {
+            for neighbor in board.adjacent(cell) {
+                assert!(
+                    board.adjacent(neighbor).contains(&cell),
+                    "{cell} {neighbor}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn walls_between_rings_and_in_a_ring() {
+        let mut board = PolarBoard::new(0, 3, 10);
+        let (cell, next, child) = (
+            board.get_index(1, 0),
+            board.get_index(1, 1),
+            board.get_index(2, 1),
+        );
+        board.remove_wall(next, cell);
+        assert!(!board.cells[cell].ccw);
+        board.remove_wall(cell, child);
+        assert!(!board.cells[child].inner);
+        assert_eq!(board.passages(cell), [next, child]);
+        assert_eq!(board.passages(child), [cell]);
+    }
+
+    #[test]
+    fn generators_make_perfect_mazes() {
+        for name in generator::ALGORITHMS {
+            let mut board = PolarBoard::new(0, 6, 10);
+            let Some(mut generator) = generator::for_topology(name, &mut board, 2) else {
+                continue;
+            };
+            headless::run(&mut board, generator.as_mut());
+            assert!(analysis::is_perfect(&board), "{name}");
+            let mut bfs = Bfs::new(&board);
+            assert!(headless::solve(&board, &mut bfs, 1000).solved, "{name}");
+        }
+    }
+}
@@ -0,0 +1,259 @@
+use std::{fs::File, io, io::BufWriter, path::Path};
+
+use crate::{
+    maze::{
+        Solver,
+        render::{Renderer, Style},
+        topology::Topology,
+    },
+    raylib::{self, Color, Vector2},
+};
+
+#[derive(Clone, Debug)]
+pub struct RasterOptions {
+    pub style: Style,
+    /// size of one cell in pixels
+    pub cell_size: f32,
+    /// empty space around the board in pixels
+    pub margin: f32,
+    pub background: Color,
+}
+
+impl Default for RasterOptions {
+    fn default() -> Self {
+        Self {
+            style: Style::default(),
+            cell_size: 10.0,
+            margin: 5.0,
+            background: raylib::BLACK,
+        }
+    }
+}
+
+/// Software renderer into a RGBA pixel buffer. Coordinates are given in
+/// board space and mapped to pixels like the svg renderer does. Text is not
+/// supported and silently skipped.
+pub struct Canvas {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u8>,
+    origin: Vector2,
+    scale: f32,
+    margin: f32,
+}
+
+impl Canvas {
+    pub fn new<T: Topology + ?Sized>(board: &T, options: &RasterOptions) -> Self {
+        let bounds = board.bounds();
+        let scale = options.cell_size / board.cell_size();
+        let width = (bounds.width * scale + 2.0 * options.margin).ceil() as usize;
+        let height = (bounds.height * scale + 2.0 * options.margin).ceil() as usize;
+        let background = options.background;
+        Self {
+            width,
+            height,
+            pixels: [background.r, background.g, background.b, background.a].repeat(width * height),
+            origin: Vector2::new(bounds.x, bounds.y),
+            scale,
+            margin: options.margin,
+        }
+    }
+
+    fn point(&self, point: Vector2) -> Vector2 {
+        Vector2::new(
+            (point.x - self.origin.x) * self.scale + self.margin,
+            (point.y - self.origin.y) * self.scale + self.margin,
+        )
+    }
+
+    /// Blend `color` over the pixel at (x, y).
+    fn blend(&mut self, x: usize, y: usize, color: Color) {
+        let index = (y * self.width + x) * 4;
+        let alpha = color.a as f32 / 255.0;
+        let pixel = &mut self.pixels[index..index + 4];
+        for (dst, src) in pixel.iter_mut().zip([color.r, color.g, color.b]) {
+            *dst = (src as f32 * alpha + *dst as f32 * (1.0 - alpha)).round() as u8;
+        }
+        pixel[3] = (color.a as f32 + pixel[3] as f32 * (1.0 - alpha)).round() as u8;
+    }
+
+    /// Visit all pixels in the bounding box and blend the ones whose
+    /// center is inside the shape.
+    fn fill<F: Fn(f32, f32) -> bool>(
+        &mut self,
+        min: Vector2,
+        max: Vector2,
+        color: Color,
+        inside: F,
+    ) {
+        let x0 = min.x.floor().max(0.0) as usize;
+        let y0 = min.y.floor().max(0.0) as usize;
+        let x1 = (max.x.ceil().max(0.0) as usize).min(self.width);
+        let y1 = (max.y.ceil().max(0.0) as usize).min(self.height);
+        for y in y0..y1 {
+            for x in x0..x1 {
+                if inside(x as f32 + 0.5, y as f32 + 0.5) {
+                    self.blend(x, y, color);
+                }
+            }
+        }
+    }
+
+    pub fn to_png(&self) -> io::Result<Vec<u8>> {
+        let mut data = Vec::new();
+        self.write_png(&mut data)?;
+        Ok(data)
+    }
+
+    pub fn write_png<W: io::Write>(&self, w: W) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(w, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels)?;
+        Ok(())
+    }
+
+    pub fn save_png<P: AsRef<Path>>(&self, file: P) -> io::Result<()> {
+        self.write_png(BufWriter::new(File::create(file)?))
+    }
+}
+
+impl Renderer for Canvas {
+    fn line(&mut self, start: Vector2, end: Vector2, thick: f32, color: Color) {
+        let (a, b) = (self.point(start), self.point(end));
+        let half = thick.max(1.0) / 2.0;
+        let (dx, dy) = (b.x - a.x, b.y - a.y);
+        let length = dx * dx + dy * dy;
+        let min = Vector2::new(a.x.min(b.x) - half, a.y.min(b.y) - half);
+        let max = Vector2::new(a.x.max(b.x) + half, a.y.max(b.y) + half);
+        self.fill(min, max, color, |x, y| {
+            // distance from the pixel center to the segment
+            let t = if length > 0.0 {
+                (((x - a.x) * dx + (y - a.y) * dy) / length).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let (px, py) = (a.x + t * dx - x, a.y + t * dy - y);
+            px * px + py * py <= half * half
+        });
+    }
+
+    fn rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: Color) {
+        let min = self.point(Vector2::new(x, y));
+        let max = self.point(Vector2::new(x + width, y + height));
+        self.fill(min, max, color, |_, _| true);
+    }
+
+    fn circle(&mut self, center: Vector2, radius: f32, color: Color) {
+        let center = self.point(center);
+        let radius = (radius * self.scale).max(0.5);
+        let min = Vector2::new(center.x - radius, center.y - radius);
+        let max = Vector2::new(center.x + radius, center.y + radius);
+        self.fill(min, max, color, |x, y| {
+            (x - center.x).powi(2) + (y - center.y).powi(2) <= radius * radius
+        });
+    }
+
+    fn triangle(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color) {
+        let (v1, v2, v3) = (self.point(v1), self.point(v2), self.point(v3));
+        let edge = |a: Vector2, b: Vector2, x: f32, y: f32| {
+            (b.x - a.x) * (y - a.y) - (b.y - a.y) * (x - a.x)
+        };
+        let min = Vector2::new(v1.x.min(v2.x).min(v3.x), v1.y.min(v2.y).min(v3.y));
+        let max = Vector2::new(v1.x.max(v2.x).max(v3.x), v1.y.max(v2.y).max(v3.y));
+        self.fill(min, max, color, |x, y| {
+            let (e1, e2, e3) = (edge(v1, v2, x, y), edge(v2, v3, x, y), edge(v3, v1, x, y));
+            (e1 >= 0.0 && e2 >= 0.0 && e3 >= 0.0) || (e1 <= 0.0 && e2 <= 0.0 && e3 <= 0.0)
+        });
+    }
+
+    fn text(&mut self, _text: &str, _x: f32, _y: f32, _size: f32, _color: Color) {}
+}
+
+/**
+ * Rasterize the board, the overlay of the solver (e.g. the djikstra weights)
+ * and the solver's current path.
+ */
+pub fn render<T: Topology>(
+    board: &T,
+    solver: Option<&dyn Solver<T>>,
+    options: &RasterOptions,
+) -> Canvas {
+    let mut canvas = Canvas::new(board, options);
+    board.draw_board(&options.style, &mut canvas);
+    if let Some(solver) = solver {
+        solver.draw(board, &mut canvas);
+        board.draw_path(solver.get_path(), &options.style, &mut canvas);
+    }
+    canvas
+}
+
+pub fn write_png<P: AsRef<Path>, T: Topology>(
+    file: P,
+    board: &T,
+    solver: Option<&dyn Solver<T>>,
+    options: &RasterOptions,
+) -> io::Result<()> {
+    render(board, solver, options).save_png(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::{
+        Board, CELL_SIZE, WALL_COLOR, generator, headless, solver::djikstra::Djikstra,
+    };
+
+    fn pixel(canvas: &Canvas, x: usize, y: usize) -> [u8; 4] {
+        let index = (y * canvas.width + x) * 4;
+        canvas.pixels[index..index + 4].try_into().unwrap()
+    }
+
+    /// Pixel at the center of every cell, for the default options.
+    fn centers(board: &Board, canvas: &Canvas) -> Vec<[u8; 4]> {
+        board
+            .cells
+            .iter()
+            .map(|cell| pixel(canvas, 5 + cell.x * 10 + 5, 5 + cell.y * 10 + 5))
+            .collect()
+    }
+
+    #[test]
+    fn canvas_and_png() {
+        let mut board = Board::new(0, 4, 3, CELL_SIZE);
+        // visited cells are not shaded, only the walls are drawn
+        for cell in &mut board.cells {
+            cell.visited = true;
+        }
+        let canvas = render(&board, None, &RasterOptions::default());
+        assert_eq!((canvas.width, canvas.height), (50, 40));
+        assert_eq!(canvas.pixels.len(), 50 * 40 * 4);
+        let wall = [WALL_COLOR.r, WALL_COLOR.g, WALL_COLOR.b, WALL_COLOR.a];
+        assert_eq!(pixel(&canvas, 5, 5), wall);
+        assert_eq!(pixel(&canvas, 0, 0), [0, 0, 0, 255]);
+        assert!(canvas.to_png().unwrap().starts_with(b"\x89PNG\r\n\x1a\n"));
+    }
+
+    #[test]
+    fn heatmap_and_path() {
+        let mut board = Board::new(0, 8, 6, CELL_SIZE);
+        let mut generator = generator::by_name("prim", &mut board, 6).unwrap();
+        headless::run(&mut board, generator.as_mut());
+        let mut solver = Djikstra::new(&board);
+        assert!(headless::solve(&board, &mut solver, 10_000).solved);
+
+        let options = RasterOptions::default();
+        let plain = centers(&board, &render(&board, None, &options));
+        let solved = centers(&board, &render(&board, Some(&solver), &options));
+        let changed = plain
+            .iter()
+            .zip(&solved)
+            .filter(|(plain, solved)| plain != solved)
+            .count();
+        // the weights are drawn besides the path
+        assert!(changed > solver.get_path().len());
+        // the path ends in the goal
+        assert_ne!(plain[board.goal], solved[board.goal]);
+    }
+}
@@ -0,0 +1,202 @@
+#[cfg(feature = "raylib")]
+use std::ffi::CString;
+
+#[cfg(feature = "raylib")]
+use crate::raylib;
+use crate::{
+    maze::{GOAL_COLOR, PATH_COLOR, STAIRS_COLOR, START_COLOR, WALL_COLOR, WRAP_COLOR},
+    raylib::{Color, Vector2},
+};
+
+/// Drawing primitives the maze code needs. Generators, solvers and the board
+/// draw through this trait so the maze library does not depend on a window.
+pub trait Renderer {
+    fn line(&mut self, start: Vector2, end: Vector2, thick: f32, color: Color);
+    fn rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: Color);
+    fn circle(&mut self, center: Vector2, radius: f32, color: Color);
+    fn triangle(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color);
+    fn text(&mut self, text: &str, x: f32, y: f32, size: f32, color: Color);
+
+    /// Arc around `center` from angle `start` to `end` (radians, counter
+    /// clockwise from the positive x axis). Drawn as short lines unless the
+    /// backend can do better.
+    fn arc(
+        &mut self,
+        center: Vector2,
+        radius: f32,
+        start: f32,
+        end: f32,
+        thick: f32,
+        color: Color,
+    ) {
+        let point = |angle: f32| {
+            Vector2::new(
+                center.x + radius * angle.cos(),
+                center.y - radius * angle.sin(),
+            )
+        };
+        let segments = ((end - start).abs() * radius / 2.0).ceil().max(1.0) as usize;
+        for i in 0..segments {
+            let a = start + (end - start) * i as f32 / segments as f32;
+            let b = start + (end - start) * (i + 1) as f32 / segments as f32;
+            self.line(point(a), point(b), thick, color);
+        }
+    }
+}
+
+/// Colors and line widths used to draw the board and the solution path.
+#[derive(Clone, Debug)]
+pub struct Style {
+    pub wall_color: Color,
+    pub wall_width: f32,
+    pub path_color: Color,
+    pub path_width: f32,
+    pub unvisited_color: Color,
+    pub start_color: Color,
+    pub goal_color: Color,
+    /// marks the openings on the edges of a wrapped board
+    pub wrap_color: Color,
+    /// marks the stairs between the levels of a layered board
+    pub stairs_color: Color,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self {
+            wall_color: WALL_COLOR,
+            wall_width: 1.0,
+            path_color: PATH_COLOR,
+            path_width: 1.0,
+            unvisited_color: Color {
+                r: 60,
+                g: 60,
+                b: 60,
+                a: 100,
+            },
+            start_color: START_COLOR,
+            goal_color: GOAL_COLOR,
+            wrap_color: WRAP_COLOR,
+            stairs_color: STAIRS_COLOR,
+        }
+    }
+}
+
+/**
+ * Draws through another renderer with every coordinate scaled around
+ * `origin`. Line widths are kept, so a board with more cells than pixels
+ * still shows its walls.
+ */
+pub struct Scaled<'a> {
+    pub renderer: &'a mut dyn Renderer,
+    pub origin: Vector2,
+    pub scale: f32,
+}
+
+impl Scaled<'_> {
+    fn point(&self, point: Vector2) -> Vector2 {
+        Vector2::new(
+            self.origin.x + (point.x - self.origin.x) * self.scale,
+            self.origin.y + (point.y - self.origin.y) * self.scale,
+        )
+    }
+}
+
+impl Renderer for Scaled<'_> {
+    fn line(&mut self, start: Vector2, end: Vector2, thick: f32, color: Color) {
+        let (start, end) = (self.point(start), self.point(end));
+        self.renderer.line(start, end, thick, color);
+    }
+
+    fn rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: Color) {
+        let corner = self.point(Vector2::new(x, y));
+        self.renderer.rect(
+            corner.x,
+            corner.y,
+            width * self.scale,
+            height * self.scale,
+            color,
+        );
+    }
+
+    fn circle(&mut self, center: Vector2, radius: f32, color: Color) {
+        let center = self.point(center);
+        self.renderer.circle(center, radius * self.scale, color);
+    }
+
+    fn triangle(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color) {
+        let (v1, v2, v3) = (self.point(v1), self.point(v2), self.point(v3));
+        self.renderer.triangle(v1, v2, v3, color);
+    }
+
+    fn text(&mut self, text: &str, x: f32, y: f32, size: f32, color: Color) {
+        let corner = self.point(Vector2::new(x, y));
+        self.renderer
+            .text(text, corner.x, corner.y, size * self.scale, color);
+    }
+}
+
+/// Renderer backend that draws into the current raylib window.
+#[cfg(feature = "raylib")]
+#[derive(Default)]
+pub struct RaylibRenderer;
+
+#[cfg(feature = "raylib")]
+impl Renderer for RaylibRenderer {
+    fn line(&mut self, start: Vector2, end: Vector2, thick: f32, color: Color) {
+        unsafe {
+            if thick <= 1.0 {
+                raylib::DrawLine(
+                    start.x as i32,
+                    start.y as i32,
+                    end.x as i32,
+                    end.y as i32,
+                    color,
+                );
+            } else {
+                raylib::DrawLineEx(start, end, thick, color);
+            }
+        }
+    }
+
+    fn rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: Color) {
+        unsafe {
+            raylib::DrawRectangle(x as i32, y as i32, width as i32, height as i32, color);
+        }
+    }
+
+    fn circle(&mut self, center: Vector2, radius: f32, color: Color) {
+        unsafe {
+            raylib::DrawCircle(center.x as i32, center.y as i32, radius, color);
+        }
+    }
+
+    fn triangle(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color) {
+        unsafe {
+            raylib::DrawTriangle(v1, v2, v3, color);
+        }
+    }
+
+    fn text(&mut self, text: &str, x: f32, y: f32, size: f32, color: Color) {
+        let text = CString::new(text).expect("cstr");
+        unsafe {
+            raylib::DrawText(text.as_ptr(), x as i32, y as i32, size as i32, color);
+        }
+    }
+}
+
+/**
+ * Same conversion as raylib's ColorFromHSV, without calling into raylib.
+ */
+pub fn color_from_hsv(hue: f32, saturation: f32, value: f32) -> Color {
+    let channel = |n: f32| {
+        let k = (n + hue / 60.0) % 6.0;
+        let k = k.min(4.0 - k).clamp(0.0, 1.0);
+        ((value - value * saturation * k) * 255.0) as u8
+    };
+    Color {
+        r: channel(5.0),
+        g: channel(3.0),
+        b: channel(1.0),
+        a: 255,
+    }
+}
@@ -1,84 +1,157 @@
-use crate::maze::{Board, Solver, State, path};
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+use crate::{
+    maze::{
+        Board, Solver, State, path,
+        render::{Renderer, color_from_hsv},
+    },
+    raylib,
+};
+
+/// Estimate of the remaining distance to the goal. All of them are
+/// admissible on a grid, `Zero` turns the search into djikstra.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Heuristic {
+    #[default]
+    Manhattan,
+    Euclidean,
+    Zero,
+}
+
+impl Heuristic {
+    /// Estimate between two cells of `board`, the shorter way around the
+    /// wrapped edges.
+    pub fn distance(&self, board: &Board, from: usize, to: usize) -> f32 {
+        let (dx, dy) = board.offset(from, to);
+        let (dx, dy) = (dx as f32, dy as f32);
+        match self {
+            Heuristic::Manhattan => dx + dy,
+            Heuristic::Euclidean => (dx * dx + dy * dy).sqrt(),
+            Heuristic::Zero => 0.0,
+        }
+    }
+}
+
+/// Entry of the open set, ordered so the `BinaryHeap` pops the lowest f
+/// score first. Ties go to the node closer to the goal.
+#[derive(Clone, Copy, Debug)]
+struct Node {
+    index: usize,
+    f: f32,
+    h: f32,
+}
+
+impl PartialEq for Node {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Node {}
+
+impl PartialOrd for Node {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Node {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .f
+            .total_cmp(&self.f)
+            .then_with(|| other.h.total_cmp(&self.h))
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
 
 pub struct AStar {
     end: usize,
-    positions: Vec<usize>,
+    heuristic: Heuristic,
+    open: BinaryHeap<Node>,
+    closed: Vec<bool>,
+    /// cost of the best known path from the start
+    g: Vec<Option<usize>>,
+    parent: Vec<Option<usize>>,
+    /// cells taken from the open set, not the ones only pushed to it
+    expanded: usize,
+    /// the cell expanded last, its path is drawn while searching
+    current: usize,
+    /// the solution, traced once the goal is reached
     pub path: Vec<usize>,
-    // rng: ThreadRng,
 }
 
 impl AStar {
     pub fn new(board: &Board) -> Self {
+        Self::with_heuristic(board, Heuristic::default())
+    }
+
+    pub fn with_heuristic(board: &Board, heuristic: Heuristic) -> Self {
+        let mut g = vec![None; board.cells.len()];
+        g[board.start] = Some(0);
+        let h = heuristic.distance(board, board.start, board.goal);
         Self {
-            end: board.get_index(board.board_size - 1, board.board_size - 1),
-            positions: vec![0],
-            path: vec![0],
-            // rng: rand::rng(),
+            end: board.goal,
+            heuristic,
+            open: BinaryHeap::from([Node {
+                index: board.start,
+                f: h,
+                h,
+            }]),
+            closed: vec![false; board.cells.len()],
+            g,
+            parent: vec![None; board.cells.len()],
+            expanded: 0,
+            current: board.start,
+            path: vec![board.start],
         }
     }
+
+    /// Follow the parents from `index` back to the start.
+    fn trace(&self, index: usize) -> Vec<usize> {
+        let mut path = vec![index];
+        while let Some(parent) = self.parent[*path.last().unwrap()] {
+            path.push(parent);
+        }
+        path.reverse();
+        path
+    }
 }
 
 impl Solver for AStar {
     fn step(&mut self, board: &Board) -> Result<State, String> {
-        let current = &board.cells[*self.path.last().unwrap()];
-        let neighbors: Option<(usize, usize)> = board
-            .neighbors(board.get_index(current.x, current.y))
-            .into_iter()
-            .enumerate()
-            .filter_map(|(i, c)| {
-                if let Some(c) = c {
-                    let neighbor = &board.cells[c];
-                    let distance =
-                        (board.board_size - neighbor.x) + (board.board_size - neighbor.y);
-                    if !self.positions.contains(&c) {
-                        match i {
-                            0 => {
-                                if !current.walls.top {
-                                    Some((c, distance))
-                                } else {
-                                    None
-                                }
-                            }
-                            1 => {
-                                if !current.walls.bottom {
-                                    Some((c, distance))
-                                } else {
-                                    None
-                                }
-                            }
-                            2 => {
-                                if !current.walls.left {
-                                    Some((c, distance))
-                                } else {
-                                    None
-                                }
-                            }
-                            3 => {
-                                if !current.walls.right {
-                                    Some((c, distance))
-                                } else {
-                                    None
-                                }
-                            }
-                            _ => None,
-                        }
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
-            })
-            .min_by(|a, b| a.1.cmp(&b.1));
-
-        if let Some((cell, _)) = neighbors {
-            self.path.push(cell);
-            self.positions.push(cell);
-            if cell == self.end {
-                return Ok(State::Done);
+        if self.closed[self.end] {
+            return Ok(State::Done);
+        }
+        // skip entries that were improved after they were pushed
+        let current = loop {
+            match self.open.pop() {
+                Some(node) if self.closed[node.index] => continue,
+                Some(node) => break node.index,
+                None => return Err(String::from("goal is not reachable")),
             }
-        } else {
-            self.path.pop();
+        };
+        self.closed[current] = true;
+        self.expanded += 1;
+        self.current = current;
+        if current == self.end {
+            self.path = self.trace(current);
+            return Ok(State::Done);
+        }
+
+        let cost = self.g[current].unwrap() + 1;
+        for neighbor in board.passages(current) {
+            if self.closed[neighbor] || self.g[neighbor].is_some_and(|g| g <= cost) {
+                continue;
+            }
+            self.g[neighbor] = Some(cost);
+            self.parent[neighbor] = Some(current);
+            let h = self.heuristic.distance(board, neighbor, self.end);
+            self.open.push(Node {
+                index: neighbor,
+                f: cost as f32 + h,
+                h,
+            });
         }
 
         Ok(State::Solve)
@@ -88,7 +161,68 @@ impl Solver for AStar {
         &self.path
     }
 
-    fn draw(&self, board: &Board) {
-        path::draw_path(board, self.get_path());
+    fn expanded(&self) -> Option<usize> {
+        Some(self.expanded)
+    }
+
+    fn draw(&self, board: &Board, renderer: &mut dyn Renderer) {
+        // closed cells are dimmed, the frontier is highlighted
+        for (index, cell) in board.cells.iter().enumerate() {
+            let color = if self.closed[index] {
+                color_from_hsv(200.0, 0.6, 0.4)
+            } else if self.g[index].is_some() {
+                color_from_hsv(50.0, 0.8, 1.0)
+            } else {
+                continue;
+            };
+            let center = raylib::Vector2::new(
+                (board.x + cell.x * board.cell_size + board.cell_size / 2) as f32,
+                (board.y + cell.y * board.cell_size + board.cell_size / 2) as f32,
+            );
+            renderer.circle(center, board.cell_size as f32 / 5.0, color);
+        }
+        path::draw_path(board, &self.trace(self.current), renderer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::{CELL_SIZE, braid, generator, headless, solver::bfs::Bfs};
+
+    #[test]
+    fn shortest_path_on_a_braided_maze() {
+        for seed in 0..4 {
+            let mut board = Board::new(0, 14, 10, CELL_SIZE);
+            let mut generator = generator::by_name("prim", &mut board, seed).unwrap();
+            headless::run(&mut board, generator.as_mut());
+            braid::braid_with_seed(&mut board, 1.0, seed);
+
+            let mut bfs = Bfs::new(&board);
+            assert!(headless::solve(&board, &mut bfs, 10_000).solved);
+            for heuristic in [Heuristic::Manhattan, Heuristic::Euclidean, Heuristic::Zero] {
+                let mut a_star = AStar::with_heuristic(&board, heuristic);
+                assert!(headless::solve(&board, &mut a_star, 10_000).solved);
+                assert_eq!(a_star.path.len(), bfs.get_path().len(), "{heuristic:?}");
+                assert!(
+                    a_star
+                        .path
+                        .windows(2)
+                        .all(|pair| board.passages(pair[0]).contains(&pair[1]))
+                );
+                assert!(a_star.expanded <= board.cells.len());
+            }
+        }
+    }
+
+    #[test]
+    fn expanded_counts_the_closed_cells() {
+        let mut board = Board::new(0, 6, 5, CELL_SIZE);
+        let mut generator = generator::by_name("wilson", &mut board, 5).unwrap();
+        headless::run(&mut board, generator.as_mut());
+        let mut a_star = AStar::new(&board);
+        headless::solve(&board, &mut a_star, 1000);
+        let closed = a_star.closed.iter().filter(|&&closed| closed).count();
+        assert_eq!(a_star.expanded(), Some(closed));
     }
 }
@@ -1,28 +1,38 @@
-use rand::{rngs::ThreadRng, seq::IndexedRandom};
+use rand::{SeedableRng, rngs::StdRng, seq::IndexedRandom};
 
-use crate::maze::{Board, Solver, State, path};
+use crate::maze::{Board, Solver, State, path, render::Renderer};
 
 pub struct Backtracker {
     end: usize,
     positions: Vec<usize>,
     pub path: Vec<usize>,
-    rng: ThreadRng,
+    rng: StdRng,
 }
 
 impl Backtracker {
     pub fn new(board: &Board) -> Self {
+        Self::with_seed(board, rand::random())
+    }
+
+    pub fn with_seed(board: &Board, seed: u64) -> Self {
         Self {
-            end: board.get_index(board.board_size - 1, board.board_size - 1),
-            positions: vec![0],
-            path: vec![0],
-            rng: rand::rng(),
+            end: board.goal,
+            positions: vec![board.start],
+            path: vec![board.start],
+            rng: StdRng::seed_from_u64(seed),
         }
     }
 }
 
 impl Solver for Backtracker {
     fn step(&mut self, board: &Board) -> Result<State, String> {
-        let current = &board.cells[*self.path.last().unwrap()];
+        let Some(&last) = self.path.last() else {
+            return Err(String::from("goal is not reachable"));
+        };
+        if last == self.end {
+            return Ok(State::Done);
+        }
+        let current = &board.cells[last];
         let neighbors: Vec<usize> = board
             .neighbors(board.get_index(current.x, current.y))
             .into_iter()
@@ -88,7 +98,32 @@ impl Solver for Backtracker {
         &self.path
     }
 
-    fn draw(&self, board: &Board) {
-        path::draw_path(board, self.get_path());
+    fn draw(&self, board: &Board, renderer: &mut dyn Renderer) {
+        path::draw_path(board, self.get_path(), renderer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::{CELL_SIZE, braid, generator, headless};
+
+    /// Number of steps and the path of a seeded run on a braided maze,
+    /// where the choices matter.
+    fn run(seed: u64) -> (usize, Vec<usize>) {
+        let mut board = Board::new(0, 12, 9, CELL_SIZE);
+        let mut generator = generator::by_name("prim", &mut board, 1).unwrap();
+        headless::run(&mut board, generator.as_mut());
+        braid::braid_with_seed(&mut board, 1.0, 1);
+        let mut solver = Backtracker::with_seed(&board, seed);
+        let stats = headless::solve(&board, &mut solver, 10_000);
+        assert!(stats.solved);
+        (stats.steps, solver.path)
+    }
+
+    #[test]
+    fn same_seed_same_walk() {
+        assert_eq!(run(3), run(3));
+        assert!((0..10).any(|seed| run(seed) != run(3)));
     }
 }
@@ -0,0 +1,111 @@
+use std::marker::PhantomData;
+
+use crate::{
+    maze::{
+        Board, Solver, State,
+        render::{Renderer, Style, color_from_hsv},
+        topology::Topology,
+    },
+    raylib,
+};
+
+/// Breadth first search, every step expands one layer. The path it finds
+/// is always a shortest one.
+pub struct Bfs<T: ?Sized = Board> {
+    end: usize,
+    frontier: Vec<usize>,
+    /// number of steps from the start, `None` for cells not reached yet
+    distance: Vec<Option<usize>>,
+    parent: Vec<Option<usize>>,
+    depth: usize,
+    expanded: usize,
+    pub path: Vec<usize>,
+    /// the grid the solver was made for
+    topology: PhantomData<fn(&T)>,
+}
+
+impl<T: Topology> Bfs<T> {
+    pub fn new(board: &T) -> Self {
+        let mut distance = vec![None; board.cell_count()];
+        distance[board.start()] = Some(0);
+        Self {
+            end: board.goal(),
+            frontier: vec![board.start()],
+            distance,
+            parent: vec![None; board.cell_count()],
+            depth: 0,
+            expanded: 1,
+            path: vec![],
+            topology: PhantomData,
+        }
+    }
+
+    /// Follow the parents from the goal back to the start.
+    fn trace(&mut self) {
+        let mut path = vec![self.end];
+        while let Some(parent) = self.parent[*path.last().unwrap()] {
+            path.push(parent);
+        }
+        path.reverse();
+        self.path = path;
+    }
+}
+
+impl<T: Topology> Solver<T> for Bfs<T> {
+    fn step(&mut self, board: &T) -> Result<State, String> {
+        if !self.path.is_empty() {
+            return Ok(State::Done);
+        }
+        if self.distance[self.end].is_some() {
+            self.trace();
+            return Ok(State::Done);
+        }
+        if self.frontier.is_empty() {
+            return Err(String::from("goal is not reachable"));
+        }
+
+        self.depth += 1;
+        let mut next = vec![];
+        for &cell in &self.frontier {
+            for neighbor in board.passages(cell) {
+                if self.distance[neighbor].is_none() {
+                    self.distance[neighbor] = Some(self.depth);
+                    self.parent[neighbor] = Some(cell);
+                    next.push(neighbor);
+                }
+            }
+        }
+        self.expanded += next.len();
+        self.frontier = next;
+        Ok(State::Solve)
+    }
+
+    fn get_path(&self) -> &Vec<usize> {
+        &self.path
+    }
+
+    fn expanded(&self) -> Option<usize> {
+        Some(self.expanded)
+    }
+
+    fn draw(&self, board: &T, renderer: &mut dyn Renderer) {
+        // reached cells get darker the closer they are to the start
+        for (index, distance) in self.distance.iter().enumerate() {
+            if let Some(distance) = distance {
+                renderer.circle(
+                    board.center(index),
+                    board.cell_size() / 5.0,
+                    color_from_hsv(
+                        190.0,
+                        0.75,
+                        0.2 + 0.6 * *distance as f32 / self.depth.max(1) as f32,
+                    ),
+                );
+            }
+        }
+        for &index in &self.frontier {
+            renderer.circle(board.center(index), board.cell_size() / 4.0, raylib::WHITE);
+        }
+        board.draw_path(&self.path, &Style::default(), renderer);
+    }
+}
@@ -0,0 +1,224 @@
+use crate::{
+    maze::{
+        Board, Solver, State, path,
+        render::{Renderer, color_from_hsv},
+    },
+    raylib,
+};
+
+/// One breadth first search of the two.
+struct Search {
+    frontier: Vec<usize>,
+    distance: Vec<Option<usize>>,
+    parent: Vec<Option<usize>>,
+    depth: usize,
+    hue: f32,
+}
+
+impl Search {
+    fn new(board: &Board, from: usize, hue: f32) -> Self {
+        let mut distance = vec![None; board.cells.len()];
+        distance[from] = Some(0);
+        Self {
+            frontier: vec![from],
+            distance,
+            parent: vec![None; board.cells.len()],
+            depth: 0,
+            hue,
+        }
+    }
+
+    /// Expand one layer, returns the number of new cells.
+    fn expand(&mut self, board: &Board) -> usize {
+        self.depth += 1;
+        let mut next = vec![];
+        for &cell in &self.frontier {
+            for neighbor in board.passages(cell) {
+                if self.distance[neighbor].is_none() {
+                    self.distance[neighbor] = Some(self.depth);
+                    self.parent[neighbor] = Some(cell);
+                    next.push(neighbor);
+                }
+            }
+        }
+        self.frontier = next;
+        self.frontier.len()
+    }
+
+    /// The cells from `index` back to where this search started.
+    fn trace(&self, index: usize) -> Vec<usize> {
+        let mut path = vec![index];
+        while let Some(parent) = self.parent[*path.last().unwrap()] {
+            path.push(parent);
+        }
+        path
+    }
+
+    fn draw(&self, board: &Board, renderer: &mut dyn Renderer) {
+        for (index, distance) in self.distance.iter().enumerate() {
+            let Some(distance) = distance else {
+                continue;
+            };
+            let cell = &board.cells[index];
+            renderer.circle(
+                raylib::Vector2::new(
+                    (board.x + cell.x * board.cell_size + board.cell_size / 2) as f32,
+                    (board.y + cell.y * board.cell_size + board.cell_size / 2) as f32,
+                ),
+                board.cell_size as f32 / 5.0,
+                color_from_hsv(
+                    self.hue,
+                    0.75,
+                    0.3 + 0.7 * *distance as f32 / self.depth.max(1) as f32,
+                ),
+            );
+        }
+    }
+}
+
+/// Two breadth first searches, one from the start and one from the goal.
+/// Every step expands one layer of both, the search stops where they meet.
+pub struct Bidirectional {
+    forward: Search,
+    backward: Search,
+    expanded: usize,
+    pub path: Vec<usize>,
+}
+
+impl Bidirectional {
+    pub fn new(board: &Board) -> Self {
+        Self {
+            forward: Search::new(board, board.start, 210.0),
+            backward: Search::new(board, board.goal, 30.0),
+            expanded: if board.start == board.goal { 1 } else { 2 },
+            path: if board.start == board.goal {
+                vec![board.start]
+            } else {
+                vec![]
+            },
+        }
+    }
+
+    /// The cell on the given frontier with the shortest way through it.
+    fn meeting(&self, frontier: &[usize]) -> Option<usize> {
+        frontier
+            .iter()
+            .copied()
+            .filter_map(|cell| {
+                let forward = self.forward.distance[cell]?;
+                let backward = self.backward.distance[cell]?;
+                Some((cell, forward + backward))
+            })
+            .min_by_key(|&(_, length)| length)
+            .map(|(cell, _)| cell)
+    }
+
+    fn join(&mut self, cell: usize) {
+        let mut path = self.forward.trace(cell);
+        path.reverse();
+        path.extend(self.backward.trace(cell).into_iter().skip(1));
+        self.path = path;
+    }
+}
+
+impl Solver for Bidirectional {
+    fn step(&mut self, board: &Board) -> Result<State, String> {
+        if !self.path.is_empty() {
+            return Ok(State::Done);
+        }
+
+        self.expanded += self.forward.expand(board);
+        if let Some(cell) = self.meeting(&self.forward.frontier) {
+            self.join(cell);
+            return Ok(State::Done);
+        }
+        self.expanded += self.backward.expand(board);
+        if let Some(cell) = self.meeting(&self.backward.frontier) {
+            self.join(cell);
+            return Ok(State::Done);
+        }
+
+        if self.forward.frontier.is_empty() || self.backward.frontier.is_empty() {
+            return Err(String::from("goal is not reachable"));
+        }
+        Ok(State::Solve)
+    }
+
+    fn get_path(&self) -> &Vec<usize> {
+        &self.path
+    }
+
+    fn expanded(&self) -> Option<usize> {
+        Some(self.expanded)
+    }
+
+    fn draw(&self, board: &Board, renderer: &mut dyn Renderer) {
+        self.forward.draw(board, renderer);
+        self.backward.draw(board, renderer);
+        path::draw_path(board, self.get_path(), renderer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::{CELL_SIZE, braid, generator, headless, solver::bfs::Bfs};
+
+    #[test]
+    fn shortest_path() {
+        for name in generator::ALGORITHMS {
+            let mut board = Board::new(0, 11, 9, CELL_SIZE);
+            let mut generator = generator::by_name(name, &mut board, 8).unwrap();
+            headless::run(&mut board, generator.as_mut());
+            braid::braid_with_seed(&mut board, 0.5, 8);
+
+            let mut bfs = Bfs::new(&board);
+            headless::solve(&board, &mut bfs, 1000);
+            let mut bidirectional = Bidirectional::new(&board);
+            assert!(headless::solve(&board, &mut bidirectional, 1000).solved);
+            let path = &bidirectional.path;
+            assert_eq!(path.len(), bfs.path.len(), "{name}");
+            assert_eq!(path.first(), Some(&board.start));
+            assert_eq!(path.last(), Some(&board.goal));
+            assert!(
+                path.windows(2)
+                    .all(|pair| board.passages(pair[0]).contains(&pair[1]))
+            );
+        }
+    }
+
+    #[test]
+    fn expands_less_than_bfs() {
+        // without inner walls bfs fills a diamond around the start, the two
+        // smaller ones meet halfway
+        let mut board = Board::new(0, 15, 15, CELL_SIZE);
+        for index in 0..board.cells.len() {
+            for neighbor in board.neighbors(index).into_iter().flatten() {
+                board.remove_wall(index, neighbor);
+            }
+        }
+        board.set_start(board.get_index(0, 7));
+        board.set_goal(board.get_index(14, 7));
+
+        let mut bfs = Bfs::new(&board);
+        headless::solve(&board, &mut bfs, 1000);
+        let mut bidirectional = Bidirectional::new(&board);
+        headless::solve(&board, &mut bidirectional, 1000);
+        assert_eq!(bidirectional.path.len(), 15);
+        assert!(bidirectional.expanded().unwrap() < bfs.expanded().unwrap());
+    }
+
+    #[test]
+    fn start_is_goal_and_unreachable_goal() {
+        let mut board = Board::new(0, 4, 4, CELL_SIZE);
+        board.set_goal(board.start);
+        let mut bidirectional = Bidirectional::new(&board);
+        assert!(matches!(bidirectional.step(&board), Ok(State::Done)));
+        assert_eq!(bidirectional.path, vec![board.start]);
+
+        // a fresh board has no passages at all
+        let board = Board::new(0, 4, 4, CELL_SIZE);
+        let stats = headless::solve(&board, &mut Bidirectional::new(&board), 1000);
+        assert_eq!(stats.error.as_deref(), Some("goal is not reachable"));
+    }
+}
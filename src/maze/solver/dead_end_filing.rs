@@ -1,9 +1,10 @@
 use crate::{
-    maze::{Board, Solver, State, path},
+    maze::{Board, Solver, State, path, render::Renderer},
     raylib,
 };
 
 pub struct DeadEndFilling {
+    start: usize,
     end: usize,
     dead_ends: Vec<usize>,
     dead_path: Vec<usize>,
@@ -28,12 +29,14 @@ impl DeadEndFilling {
             if cell.walls.right {
                 walls += 1;
             }
-            if walls == 3 {
-                dead_ends.push(board.get_index(cell.x, cell.y));
+            let index = board.get_index(cell.x, cell.y);
+            if walls == 3 && index != board.start && index != board.goal {
+                dead_ends.push(index);
             }
         }
         Self {
-            end: board.get_index(board.board_size - 1, board.board_size - 1),
+            start: board.start,
+            end: board.goal,
             dead_ends,
             dead_path: vec![],
             path: vec![],
@@ -95,17 +98,15 @@ impl Solver for DeadEndFilling {
                 .collect();
 
             if neighbors.len() == 1 {
-                let next = &board.cells[*neighbors.first().unwrap()];
-                if !(next.x == board.board_size - 1 && next.y == board.board_size - 1
-                    || next.x == 0 && next.y == 0)
-                {
-                    self.dead_ends.push(*neighbors.first().unwrap());
+                let next = *neighbors.first().unwrap();
+                if next != self.end && next != self.start {
+                    self.dead_ends.push(next);
                 }
                 self.dead_path.push(cell);
             }
         } else {
             if self.path.is_empty() {
-                self.path.push(0);
+                self.path.push(self.start);
             }
             let index = self.path.last().unwrap();
             if *index == self.end {
@@ -159,10 +160,18 @@ impl Solver for DeadEndFilling {
                 })
                 .collect();
 
-            if neighbors.len() != 1 {
-                return Err(format!("neighbors is: {:?}", neighbors));
+            // a perfect maze leaves a single corridor, the loops of a
+            // braided one are walked like a depth first search
+            match neighbors.first() {
+                Some(&next) => self.path.push(next),
+                None if self.path.len() == 1 => {
+                    return Err(String::from("goal is not reachable"));
+                }
+                None => {
+                    let dead = self.path.pop().unwrap();
+                    self.dead_path.push(dead);
+                }
             }
-            self.path.push(*neighbors.first().unwrap());
         }
 
         Ok(State::Solve)
@@ -172,33 +181,84 @@ impl Solver for DeadEndFilling {
         &self.path
     }
 
-    fn draw(&self, board: &Board) {
-        unsafe {
-            for index in &self.dead_path {
-                let cell = &board.cells[*index];
-                raylib::DrawLine(
-                    (board.x + cell.x * board.cell_size + 1) as i32,
-                    (board.y + cell.y * board.cell_size + 1) as i32,
-                    (board.x + cell.x * board.cell_size + board.cell_size - 1) as i32,
-                    (board.y + cell.y * board.cell_size + board.cell_size - 1) as i32,
-                    raylib::RED,
-                );
-                raylib::DrawLine(
-                    (board.x + cell.x * board.cell_size + board.cell_size - 1) as i32,
-                    (board.y + cell.y * board.cell_size + 1) as i32,
-                    (board.x + cell.x * board.cell_size + 1) as i32,
-                    (board.y + cell.y * board.cell_size + board.cell_size - 1) as i32,
-                    raylib::RED,
-                );
-            }
-            let current = &board.cells[self.current as usize];
-            raylib::DrawCircle(
-                (board.x + current.x * board.cell_size + board.cell_size / 2) as i32,
-                (board.y + current.y * board.cell_size + board.cell_size / 2) as i32,
-                board.cell_size as f32 / 5.0,
-                raylib::GREEN,
+    fn draw(&self, board: &Board, renderer: &mut dyn Renderer) {
+        for index in &self.dead_path {
+            let cell = &board.cells[*index];
+            let left = (board.x + cell.x * board.cell_size + 1) as f32;
+            let top = (board.y + cell.y * board.cell_size + 1) as f32;
+            let right = (board.x + cell.x * board.cell_size + board.cell_size - 1) as f32;
+            let bottom = (board.y + cell.y * board.cell_size + board.cell_size - 1) as f32;
+            renderer.line(
+                raylib::Vector2::new(left, top),
+                raylib::Vector2::new(right, bottom),
+                1.0,
+                raylib::RED,
+            );
+            renderer.line(
+                raylib::Vector2::new(right, top),
+                raylib::Vector2::new(left, bottom),
+                1.0,
+                raylib::RED,
             );
-            path::draw_path(board, self.get_path());
         }
+        let current = &board.cells[self.current as usize];
+        renderer.circle(
+            raylib::Vector2::new(
+                (board.x + current.x * board.cell_size + board.cell_size / 2) as f32,
+                (board.y + current.y * board.cell_size + board.cell_size / 2) as f32,
+            ),
+            board.cell_size as f32 / 5.0,
+            raylib::GREEN,
+        );
+        path::draw_path(board, self.get_path(), renderer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::{CELL_SIZE, braid, generator, headless, solver::bfs::Bfs};
+
+    #[test]
+    fn fills_a_perfect_maze() {
+        for name in generator::ALGORITHMS {
+            let mut board = Board::new(0, 10, 8, CELL_SIZE);
+            let mut generator = generator::by_name(name, &mut board, 3).unwrap();
+            headless::run(&mut board, generator.as_mut());
+            let mut bfs = Bfs::new(&board);
+            headless::solve(&board, &mut bfs, 1000);
+            let mut filling = DeadEndFilling::new(&board);
+            let stats = headless::solve(&board, &mut filling, 10_000);
+            assert!(stats.solved, "{name}: {:?}", stats.error);
+            assert_eq!(&filling.path, bfs.get_path(), "{name}");
+        }
+    }
+
+    #[test]
+    fn walks_the_loops_of_a_braided_maze() {
+        for seed in 0..10 {
+            let mut board = Board::new(0, 10, 8, CELL_SIZE);
+            let mut generator =
+                generator::by_name("recursive backtracker", &mut board, seed).unwrap();
+            headless::run(&mut board, generator.as_mut());
+            braid::braid_with_seed(&mut board, 0.7, seed);
+            let mut filling = DeadEndFilling::new(&board);
+            let stats = headless::solve(&board, &mut filling, 10_000);
+            assert!(stats.solved, "seed {seed}: {:?}", stats.error);
+            let path = &filling.path;
+            assert_eq!(path.first(), Some(&board.start));
+            assert_eq!(path.last(), Some(&board.goal));
+            assert!(
+                path.windows(2)
+                    .all(|pair| board.passages(pair[0]).contains(&pair[1]))
+            );
+        }
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        let board = Board::new(0, 4, 3, CELL_SIZE);
+        let stats = headless::solve(&board, &mut DeadEndFilling::new(&board), 1000);
+        assert_eq!(stats.error.as_deref(), Some("goal is not reachable"));
     }
 }
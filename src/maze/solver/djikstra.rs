@@ -1,125 +1,80 @@
+use std::marker::PhantomData;
+
 use crate::{
-    maze::{Board, Solver, State},
+    maze::{
+        Board, Solver, State,
+        render::{Renderer, color_from_hsv},
+        topology::Topology,
+    },
     raylib,
 };
 
-#[derive(Default, Clone, Copy, Debug)]
-pub struct Weight {
-    pub x: usize,
-    pub y: usize,
-    pub weight: usize,
-}
-
-pub struct Djikstra {
-    start: (usize, usize),
-    end: (usize, usize),
+pub struct Djikstra<T: ?Sized = Board> {
+    start: usize,
+    end: usize,
     positions: Vec<usize>,
     pub path: Vec<usize>,
-    pub weights: Vec<Option<Weight>>,
+    /// distance from the start plus one, `None` for cells not reached yet
+    pub weights: Vec<Option<usize>>,
+    max_weight: usize,
+    expanded: usize,
     pub reached_end: bool,
     pub solved: bool,
+    /// the grid the solver was made for
+    topology: PhantomData<fn(&T)>,
 }
 
-impl Djikstra {
-    pub fn new(board: &Board) -> Self {
-        let mut weights = vec![None; (board.board_size) * (board.board_size)];
-        weights[0] = Some(Weight {
-            x: 0,
-            y: 0,
-            weight: 1,
-        });
+impl<T: Topology> Djikstra<T> {
+    pub fn new(board: &T) -> Self {
+        let (start, end) = (board.start(), board.goal());
+        let mut weights = vec![None; board.cell_count()];
+        weights[start] = Some(1);
         Self {
-            start: (0, 0),
-            end: (board.board_size - 1, board.board_size - 1),
-            positions: vec![0],
-            path: vec![],
+            start,
+            end,
+            positions: vec![start],
+            path: if start == end { vec![start] } else { vec![] },
             weights,
-            reached_end: false,
-            solved: false,
+            max_weight: 1,
+            expanded: 1,
+            reached_end: start == end,
+            solved: start == end,
+            topology: PhantomData,
         }
     }
 
-    fn get_max_weight(&self) -> usize {
-        self.weights
-            .iter()
-            .flatten()
-            .max_by(|a, b| a.weight.cmp(&b.weight))
-            .unwrap()
-            .weight
-    }
-
-    fn search_path(&mut self, board: &Board) -> State {
+    fn search_path(&mut self, board: &T) -> State {
         let mut next_cells: Vec<usize> = vec![];
-        for index in &self.positions {
-            let weight = self.weights[*index].unwrap();
-            let neighbors = board.neighbors(*index);
-            let free: Vec<(usize, &Option<usize>)> = neighbors
-                .iter()
-                .enumerate()
-                .filter(|&(d, i)| {
-                    if (i.is_some() && self.weights[i.unwrap()].is_none())
-                        && ((d == 0 && !board.cells[*index].walls.top)
-                            || (d == 1 && !board.cells[*index].walls.bottom)
-                            || (d == 2 && !board.cells[*index].walls.left)
-                            || (d == 3 && !board.cells[*index].walls.right))
-                    {
-                        return true;
-                    }
-                    false
-                })
-                .collect();
-
-            if !free.is_empty() {
-                for (_, j) in free {
-                    self.weights[j.unwrap()] = Some(Weight {
-                        x: board.cells[j.unwrap()].x,
-                        y: board.cells[j.unwrap()].y,
-                        weight: weight.weight + 1,
-                    });
-                    next_cells.push(j.unwrap());
-                    if self.weights[j.unwrap()].unwrap().x == self.end.0
-                        && self.weights[j.unwrap()].unwrap().y == self.end.1
-                    {
-                        self.reached_end = true;
-                        self.path.push(j.unwrap())
-                    }
+        for &index in &self.positions {
+            let weight = self.weights[index].unwrap();
+            for next in board.passages(index) {
+                if self.weights[next].is_some() {
+                    continue;
+                }
+                self.weights[next] = Some(weight + 1);
+                self.max_weight = self.max_weight.max(weight + 1);
+                next_cells.push(next);
+                self.expanded += 1;
+                if next == self.end {
+                    self.reached_end = true;
+                    self.path.push(next)
                 }
             }
         }
         self.positions = next_cells;
         State::Solve
     }
-    fn path(&mut self, board: &Board) -> State {
+
+    fn path(&mut self, board: &T) -> State {
         let index: usize = *self.path.last().unwrap();
-        let neighbors = board.neighbors(index);
-        let mut free: Vec<(usize, &Option<usize>)> = neighbors
-            .iter()
-            .enumerate()
-            .filter(|&(d, i)| {
-                if (i.is_some()
-                    && !self.path.contains(&i.unwrap())
-                    && self.weights[i.unwrap()].is_some())
-                    && ((d == 0 && !board.cells[index].walls.top)
-                        || (d == 1 && !board.cells[index].walls.bottom)
-                        || (d == 2 && !board.cells[index].walls.left)
-                        || (d == 3 && !board.cells[index].walls.right))
-                {
-                    return true;
-                }
-                false
-            })
-            .collect();
-        free.sort_by(|a, b| {
-            self.weights[a.1.unwrap()]
-                .unwrap()
-                .weight
-                .cmp(&self.weights[b.1.unwrap()].unwrap().weight)
-        });
-        if let Some(next) = free.first() {
-            self.path.push(next.1.unwrap());
-            if self.weights[next.1.unwrap()].unwrap().x == self.start.0
-                && self.weights[next.1.unwrap()].unwrap().y == self.start.1
-            {
+        let next = board
+            .passages(index)
+            .into_iter()
+            .filter(|next| !self.path.contains(next) && self.weights[*next].is_some())
+            .min_by_key(|next| self.weights[*next].unwrap());
+        if let Some(next) = next {
+            self.path.push(next);
+            if next == self.start {
                 self.solved = true;
                 return State::Solve;
             }
@@ -129,16 +84,17 @@ impl Djikstra {
     }
 }
 
-impl Solver for Djikstra {
-    fn step(&mut self, board: &Board) -> Result<State, String> {
+impl<T: Topology> Solver<T> for Djikstra<T> {
+    fn step(&mut self, board: &T) -> Result<State, String> {
         if self.solved {
             Ok(State::Done)
         } else if !self.reached_end {
+            if self.positions.is_empty() {
+                return Err(String::from("goal is not reachable"));
+            }
             Ok(self.search_path(board))
-        } else if !self.solved {
-            Ok(self.path(board))
         } else {
-            panic!("unknown state")
+            Ok(self.path(board))
         }
     }
 
@@ -146,32 +102,23 @@ impl Solver for Djikstra {
         &self.path
     }
 
-    fn draw(&self, board: &Board) {
-        // draw the result
-        unsafe {
-            if !self.solved {
-                for (index, weight) in self.weights.iter().enumerate() {
-                    if let Some(weight) = weight {
-                        if self.path.contains(&index) {
-                            raylib::DrawCircle(
-                                (board.x + weight.x * board.cell_size + board.cell_size / 2) as i32,
-                                (board.y + weight.y * board.cell_size + board.cell_size / 2) as i32,
-                                board.cell_size as f32 / 5.0,
-                                raylib::WHITE,
-                            );
-                        } else {
-                            raylib::DrawCircle(
-                                (board.x + weight.x * board.cell_size + board.cell_size / 2) as i32,
-                                (board.y + weight.y * board.cell_size + board.cell_size / 2) as i32,
-                                board.cell_size as f32 / 5.0,
-                                raylib::ColorFromHSV(
-                                    115.0,
-                                    0.75,
-                                    1.0 / self.get_max_weight() as f32 * weight.weight as f32,
-                                ),
-                            );
-                        }
-                    }
+    fn expanded(&self) -> Option<usize> {
+        Some(self.expanded)
+    }
+
+    fn draw(&self, board: &T, renderer: &mut dyn Renderer) {
+        // the distance heatmap, it stays after solving to show the result
+        for (index, weight) in self.weights.iter().enumerate() {
+            if let Some(weight) = weight {
+                let center = board.center(index);
+                if self.path.contains(&index) {
+                    renderer.circle(center, board.cell_size() / 5.0, raylib::WHITE);
+                } else {
+                    renderer.circle(
+                        center,
+                        board.cell_size() / 5.0,
+                        color_from_hsv(115.0, 0.75, 1.0 / self.max_weight as f32 * *weight as f32),
+                    );
                 }
             }
         }
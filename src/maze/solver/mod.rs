@@ -1,4 +1,113 @@
 pub mod a_star;
 pub mod backtracker;
+pub mod bfs;
+pub mod bidirectional;
 pub mod dead_end_filing;
 pub mod djikstra;
+pub mod pledge;
+pub mod random_mouse;
+pub mod tremaux;
+pub mod wall_follower;
+
+use crate::{
+    maze::{
+        Board, Direction, Solver, render::Renderer, solver::a_star::Heuristic,
+        solver::wall_follower::Hand, topology::Topology,
+    },
+    raylib::{self, Vector2},
+};
+
+/// Names of the solvers, in the order the gui lists them.
+pub const SOLVERS: [&str; 13] = [
+    "djikstra",
+    "recursive backtracker",
+    "a* manhattan",
+    "a* euclidean",
+    "a* zero",
+    "dead end filling",
+    "breadth first search",
+    "left hand",
+    "right hand",
+    "tremaux",
+    "bidirectional",
+    "pledge",
+    "random mouse",
+];
+
+/// Create the solver called `name` (one of `SOLVERS`) for `board`. The seed
+/// is only used by the solvers that make random choices.
+pub fn by_name(name: &str, board: &Board, seed: u64) -> Option<Box<dyn Solver>> {
+    let solver: Box<dyn Solver> = match name {
+        "djikstra" => Box::new(djikstra::Djikstra::new(board)),
+        "recursive backtracker" => Box::new(backtracker::Backtracker::with_seed(board, seed)),
+        "a* manhattan" => Box::new(a_star::AStar::new(board)),
+        "a* euclidean" => Box::new(a_star::AStar::with_heuristic(board, Heuristic::Euclidean)),
+        "a* zero" => Box::new(a_star::AStar::with_heuristic(board, Heuristic::Zero)),
+        "dead end filling" => Box::new(dead_end_filing::DeadEndFilling::new(board)),
+        "breadth first search" => Box::new(bfs::Bfs::new(board)),
+        "left hand" => Box::new(wall_follower::WallFollower::new(board, Hand::Left)),
+        "right hand" => Box::new(wall_follower::WallFollower::new(board, Hand::Right)),
+        "tremaux" => Box::new(tremaux::Tremaux::new(board)),
+        "bidirectional" => Box::new(bidirectional::Bidirectional::new(board)),
+        "pledge" => Box::new(pledge::Pledge::new(board)),
+        "random mouse" => Box::new(random_mouse::RandomMouse::with_seed(
+            board,
+            100 * board.cells.len(),
+            seed,
+        )),
+        _ => return None,
+    };
+    Some(solver)
+}
+
+/// Create the solver called `name` for any grid, `None` for the solvers
+/// that only work on the square `Board`.
+pub fn for_topology<T: Topology + 'static>(name: &str, board: &T) -> Option<Box<dyn Solver<T>>> {
+    let solver: Box<dyn Solver<T>> = match name {
+        "djikstra" => Box::new(djikstra::Djikstra::new(board)),
+        "breadth first search" => Box::new(bfs::Bfs::new(board)),
+        _ => return None,
+    };
+    Some(solver)
+}
+
+/**
+ * Move the path of a walking solver on to `cell`. Coming back to a cell
+ * that is already on the path erases the detour since then.
+ */
+pub(crate) fn push_or_truncate(path: &mut Vec<usize>, cell: usize) {
+    if let Some(index) = path.iter().position(|&other| other == cell) {
+        path.truncate(index + 1);
+    } else {
+        path.push(cell);
+    }
+}
+
+/**
+ * draw a walking solver as a triangle pointing in the walking direction
+ */
+pub(crate) fn draw_agent(
+    board: &Board,
+    index: usize,
+    heading: Direction,
+    renderer: &mut dyn Renderer,
+) {
+    let cell = &board.cells[index];
+    let size = board.cell_size as f32;
+    let center = Vector2::new(
+        board.x as f32 + (cell.x as f32 + 0.5) * size,
+        board.y as f32 + (cell.y as f32 + 0.5) * size,
+    );
+    let (dx, dy) = match heading {
+        Direction::North => (0.0, -1.0),
+        Direction::South => (0.0, 1.0),
+        Direction::West => (-1.0, 0.0),
+        Direction::East => (1.0, 0.0),
+    };
+    let r = size / 3.0;
+    let tip = Vector2::new(center.x + dx * r, center.y + dy * r);
+    let back = Vector2::new(center.x - dx * r * 0.6, center.y - dy * r * 0.6);
+    let side1 = Vector2::new(back.x - dy * r * 0.7, back.y + dx * r * 0.7);
+    let side2 = Vector2::new(back.x + dy * r * 0.7, back.y - dx * r * 0.7);
+    renderer.triangle(side1, tip, side2, raylib::WHITE);
+}
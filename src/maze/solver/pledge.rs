@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+
+use crate::maze::{
+    Board, Direction, Solver, State, path,
+    render::Renderer,
+    solver::{draw_agent, push_or_truncate},
+};
+
+/// The Pledge algorithm: walk in a preferred direction, and when a wall is
+/// hit follow it with the left hand until the sum of all turns is zero
+/// again. Counting the turns lets it leave islands a plain wall follower
+/// circles forever. It is made for escaping a maze, so a goal inside the
+/// maze is not always found.
+pub struct Pledge {
+    end: usize,
+    position: usize,
+    heading: Direction,
+    /// left turns minus right turns since the wall was hit
+    turns: i32,
+    /// the turn counter in every cell and heading walked through, kept
+    /// apart for walking freely and only since the wall was hit for
+    /// following it
+    seen: HashMap<(usize, Direction, bool), i32>,
+    pub path: Vec<usize>,
+}
+
+fn turn(direction: Direction, turns: i32) -> Direction {
+    (0..turns.rem_euclid(4)).fold(direction, |direction, _| direction.turn_left())
+}
+
+impl Pledge {
+    pub fn new(board: &Board) -> Self {
+        // head for the goal along the longer axis
+        let start = &board.cells[board.start];
+        let goal = &board.cells[board.goal];
+        let heading = if start.x.abs_diff(goal.x) >= start.y.abs_diff(goal.y) {
+            if goal.x < start.x {
+                Direction::West
+            } else {
+                Direction::East
+            }
+        } else if goal.y < start.y {
+            Direction::North
+        } else {
+            Direction::South
+        };
+        Self {
+            end: board.goal,
+            position: board.start,
+            heading,
+            turns: 0,
+            seen: HashMap::new(),
+            path: vec![board.start],
+        }
+    }
+}
+
+impl Solver for Pledge {
+    fn step(&mut self, board: &Board) -> Result<State, String> {
+        if self.position == self.end {
+            return Ok(State::Done);
+        }
+        // the same place with the same count is a loop, with a count further
+        // from zero the walk circles an island and the counter runs away
+        let key = (self.position, self.heading, self.turns == 0);
+        if let Some(previous) = self.seen.insert(key, self.turns)
+            && (previous == self.turns
+                || (previous.signum() == self.turns.signum() && previous.abs() < self.turns.abs()))
+        {
+            return Err(String::from("pledge is walking in a loop"));
+        }
+
+        // walking freely only straight on or away from the wall, following
+        // the wall the left hand comes first
+        let deltas: &[i32] = if self.turns == 0 {
+            &[0, -1, -2, -3]
+        } else {
+            &[1, 0, -1, -2]
+        };
+        let walls = &board.cells[self.position].walls;
+        let Some((delta, next)) = deltas.iter().find_map(|&delta| {
+            let direction = turn(self.heading, delta);
+            if walls.has(direction) {
+                return None;
+            }
+            board
+                .neighbor(self.position, direction)
+                .map(|next| (delta, next))
+        }) else {
+            return Err(String::from("start cell is closed in"));
+        };
+
+        if self.turns != 0 && self.turns + delta == 0 {
+            self.seen.retain(|&(_, _, free), _| free);
+        }
+        self.turns += delta;
+        self.heading = turn(self.heading, delta);
+        self.position = next;
+        push_or_truncate(&mut self.path, next);
+
+        if next == self.end {
+            return Ok(State::Done);
+        }
+        Ok(State::Solve)
+    }
+
+    fn get_path(&self) -> &Vec<usize> {
+        &self.path
+    }
+
+    fn draw(&self, board: &Board, renderer: &mut dyn Renderer) {
+        path::draw_path(board, self.get_path(), renderer);
+        draw_agent(board, self.position, self.heading, renderer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::{Wrap, braid, generator::backtracking::Backtracking, headless};
+
+    #[test]
+    fn stops_on_braided_maze() {
+        for seed in 0..50 {
+            let mut board = Board::new(0, 12, 12, 10);
+            headless::run(&mut board, &mut Backtracking::with_seed(seed));
+            braid::braid_with_seed(&mut board, 1.0, seed);
+            board.set_goal(board.get_index(6, 6));
+
+            let mut pledge = Pledge::new(&board);
+            let finished = (0..1_000_000).any(|_| !matches!(pledge.step(&board), Ok(State::Solve)));
+            assert!(finished, "pledge is still walking with seed {seed}");
+        }
+    }
+
+    #[test]
+    fn stops_on_a_wrapped_corridor() {
+        // walking east on a torus comes back round without ever turning
+        let mut board = Board::with_wrap(0, 6, 3, 10, Wrap::Torus);
+        for x in 0..6 {
+            let index = board.get_index(x, 1);
+            board.remove_wall(index, board.get_index((x + 1) % 6, 1));
+        }
+        board.set_start(board.get_index(0, 1));
+        board.set_goal(board.get_index(2, 0));
+
+        let mut pledge = Pledge::new(&board);
+        pledge.heading = Direction::East;
+        let stats = headless::solve(&board, &mut pledge, 1000);
+        assert_eq!(stats.error.as_deref(), Some("pledge is walking in a loop"));
+        assert!(stats.steps <= 7);
+    }
+
+    #[test]
+    fn solves_perfect_mazes() {
+        for seed in 0..10 {
+            let mut board = Board::new(0, 10, 8, 10);
+            headless::run(&mut board, &mut Backtracking::with_seed(seed));
+            let mut pledge = Pledge::new(&board);
+            let stats = headless::solve(&board, &mut pledge, 100_000);
+            assert!(stats.solved, "seed {seed}: {:?}", stats.error);
+            assert_eq!(pledge.path.last(), Some(&board.goal));
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+use crate::maze::{
+    Board, Direction, Solver, State, path,
+    render::Renderer,
+    solver::{draw_agent, push_or_truncate},
+};
+
+/// Wanders through the maze at random and only turns around in dead ends.
+/// Gives up when the goal is not found within the step budget.
+pub struct RandomMouse {
+    end: usize,
+    position: usize,
+    previous: Option<usize>,
+    heading: Direction,
+    budget: usize,
+    steps: usize,
+    rng: StdRng,
+    pub path: Vec<usize>,
+}
+
+impl RandomMouse {
+    pub fn new(board: &Board, budget: usize) -> Self {
+        Self::with_seed(board, budget, rand::random())
+    }
+
+    pub fn with_seed(board: &Board, budget: usize, seed: u64) -> Self {
+        Self {
+            end: board.goal,
+            position: board.start,
+            previous: None,
+            heading: Direction::East,
+            budget,
+            steps: 0,
+            rng: StdRng::seed_from_u64(seed),
+            path: vec![board.start],
+        }
+    }
+}
+
+impl Solver for RandomMouse {
+    fn step(&mut self, board: &Board) -> Result<State, String> {
+        if self.position == self.end {
+            return Ok(State::Done);
+        }
+        if self.steps >= self.budget {
+            return Err(format!("goal not found within {} steps", self.budget));
+        }
+
+        let passages = board.passages(self.position);
+        let forward: Vec<usize> = passages
+            .iter()
+            .copied()
+            .filter(|&cell| Some(cell) != self.previous)
+            .collect();
+        let next = if forward.is_empty() {
+            match passages.first() {
+                Some(&back) => back,
+                None => return Err(String::from("start cell is closed in")),
+            }
+        } else {
+            forward[self.rng.random_range(0..forward.len())]
+        };
+
+        self.steps += 1;
+        self.heading = board.side(self.position, next);
+        self.previous = Some(self.position);
+        self.position = next;
+        push_or_truncate(&mut self.path, next);
+
+        if next == self.end {
+            return Ok(State::Done);
+        }
+        Ok(State::Solve)
+    }
+
+    fn get_path(&self) -> &Vec<usize> {
+        &self.path
+    }
+
+    fn draw(&self, board: &Board, renderer: &mut dyn Renderer) {
+        path::draw_path(board, self.get_path(), renderer);
+        draw_agent(board, self.position, self.heading, renderer);
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{
+    maze::{
+        Board, Direction, Solver, State, path,
+        render::Renderer,
+        solver::{draw_agent, push_or_truncate},
+    },
+    raylib::{self, Color, Vector2},
+};
+
+const MARK_ONCE: Color = Color {
+    r: 255,
+    g: 220,
+    b: 0,
+    a: 255,
+};
+
+/// Trémaux's algorithm: every passage is marked when it is walked through
+/// and no passage is walked more than twice. Works on mazes with loops, the
+/// passages marked once form the way back to the start.
+pub struct Tremaux {
+    end: usize,
+    position: usize,
+    /// side of the current cell we came in through
+    entrance: Option<usize>,
+    heading: Direction,
+    /// marks on the sides [top, bottom, left, right] of every cell, both
+    /// cells of a passage carry the same mark
+    marks: Vec<[u8; 4]>,
+    pub path: Vec<usize>,
+}
+
+impl Tremaux {
+    pub fn new(board: &Board) -> Self {
+        Self {
+            end: board.goal,
+            position: board.start,
+            entrance: None,
+            heading: Direction::East,
+            marks: vec![[0; 4]; board.cells.len()],
+            path: vec![board.start],
+        }
+    }
+
+    fn choose(&self, sides: &[usize]) -> Option<usize> {
+        let marks = &self.marks[self.position];
+        let others = || {
+            sides
+                .iter()
+                .copied()
+                .filter(|&side| Some(side) != self.entrance)
+        };
+
+        if let Some(entrance) = self.entrance {
+            // reached a known place through a new passage: turn around
+            let visited = others().any(|side| marks[side] > 0);
+            if visited && marks[entrance] == 1 {
+                return Some(entrance);
+            }
+        }
+        others()
+            .find(|&side| marks[side] == 0)
+            .or(self.entrance.filter(|&entrance| marks[entrance] < 2))
+            .or(sides.iter().copied().find(|&side| marks[side] < 2))
+    }
+}
+
+impl Solver for Tremaux {
+    fn step(&mut self, board: &Board) -> Result<State, String> {
+        if self.position == self.end {
+            return Ok(State::Done);
+        }
+        let walls = &board.cells[self.position].walls;
+        let open = [!walls.top, !walls.bottom, !walls.left, !walls.right];
+        let neighbors = board.neighbors(self.position);
+        let sides: Vec<usize> = (0..4)
+            .filter(|&side| open[side] && neighbors[side].is_some())
+            .collect();
+
+        let Some(side) = self.choose(&sides) else {
+            return Err(String::from("goal is not reachable"));
+        };
+        let next = neighbors[side].unwrap();
+        // top <-> bottom and left <-> right
+        self.marks[self.position][side] += 1;
+        self.marks[next][side ^ 1] += 1;
+        self.entrance = Some(side ^ 1);
+        self.heading = board.side(self.position, next);
+        self.position = next;
+
+        push_or_truncate(&mut self.path, next);
+
+        if next == self.end {
+            return Ok(State::Done);
+        }
+        Ok(State::Solve)
+    }
+
+    fn get_path(&self) -> &Vec<usize> {
+        &self.path
+    }
+
+    fn draw(&self, board: &Board, renderer: &mut dyn Renderer) {
+        let size = board.cell_size as f32;
+        // every passage is drawn once, from the cell above or to the left
+        for (cell, marks) in board.cells.iter().zip(&self.marks) {
+            let x = board.x as f32 + cell.x as f32 * size;
+            let y = board.y as f32 + cell.y as f32 * size;
+            for (side, center) in [
+                (1, Vector2::new(x + size / 2.0, y + size)),
+                (3, Vector2::new(x + size, y + size / 2.0)),
+            ] {
+                let color = match marks[side] {
+                    0 => continue,
+                    1 => MARK_ONCE,
+                    _ => raylib::RED,
+                };
+                renderer.circle(center, size / 8.0, color);
+            }
+        }
+        path::draw_path(board, self.get_path(), renderer);
+        draw_agent(board, self.position, self.heading, renderer);
+    }
+}
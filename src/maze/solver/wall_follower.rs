@@ -0,0 +1,146 @@
+use crate::maze::{
+    Board, Direction, Solver, State, path,
+    render::Renderer,
+    solver::{draw_agent, push_or_truncate},
+};
+
+/// The hand that is kept on the wall.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hand {
+    Left,
+    Right,
+}
+
+/// Walks through the maze keeping one hand on the wall. This always works
+/// for perfect mazes but may circle around an island forever when the maze
+/// has loops.
+pub struct WallFollower {
+    hand: Hand,
+    end: usize,
+    position: usize,
+    heading: Direction,
+    /// headings already used to leave every cell, to detect loops
+    seen: Vec<[bool; 4]>,
+    pub path: Vec<usize>,
+}
+
+fn heading_index(heading: Direction) -> usize {
+    match heading {
+        Direction::North => 0,
+        Direction::South => 1,
+        Direction::West => 2,
+        Direction::East => 3,
+    }
+}
+
+impl WallFollower {
+    pub fn new(board: &Board, hand: Hand) -> Self {
+        Self {
+            hand,
+            end: board.goal,
+            position: board.start,
+            heading: Direction::East,
+            seen: vec![[false; 4]; board.cells.len()],
+            path: vec![board.start],
+        }
+    }
+
+    /// The directions to try, in order: towards the hand, straight on,
+    /// away from the hand and back.
+    fn candidates(&self) -> [Direction; 4] {
+        let (near, far) = match self.hand {
+            Hand::Left => (self.heading.turn_left(), self.heading.turn_right()),
+            Hand::Right => (self.heading.turn_right(), self.heading.turn_left()),
+        };
+        [near, self.heading, far, self.heading.reverse()]
+    }
+}
+
+impl Solver for WallFollower {
+    fn step(&mut self, board: &Board) -> Result<State, String> {
+        if self.position == self.end {
+            return Ok(State::Done);
+        }
+        let walls = &board.cells[self.position].walls;
+        let Some((heading, next)) = self.candidates().into_iter().find_map(|direction| {
+            if walls.has(direction) {
+                return None;
+            }
+            board
+                .neighbor(self.position, direction)
+                .map(|next| (direction, next))
+        }) else {
+            return Err(String::from("start cell is closed in"));
+        };
+
+        let seen = &mut self.seen[self.position][heading_index(heading)];
+        if *seen {
+            return Err(String::from("wall follower is walking in a loop"));
+        }
+        *seen = true;
+
+        self.heading = heading;
+        self.position = next;
+        // walking back erases the dead end from the path
+        push_or_truncate(&mut self.path, next);
+
+        if next == self.end {
+            return Ok(State::Done);
+        }
+        Ok(State::Solve)
+    }
+
+    fn get_path(&self) -> &Vec<usize> {
+        &self.path
+    }
+
+    fn draw(&self, board: &Board, renderer: &mut dyn Renderer) {
+        path::draw_path(board, self.get_path(), renderer);
+
+        draw_agent(board, self.position, self.heading, renderer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::{CELL_SIZE, generator, headless, solver::bfs::Bfs};
+
+    #[test]
+    fn follows_the_solution_of_a_perfect_maze() {
+        for name in generator::ALGORITHMS {
+            let mut board = Board::new(0, 9, 7, CELL_SIZE);
+            let mut generator = generator::by_name(name, &mut board, 6).unwrap();
+            headless::run(&mut board, generator.as_mut());
+            let mut bfs = Bfs::new(&board);
+            headless::solve(&board, &mut bfs, 1000);
+            for hand in [Hand::Left, Hand::Right] {
+                let mut follower = WallFollower::new(&board, hand);
+                let stats = headless::solve(&board, &mut follower, 1000);
+                assert!(stats.solved, "{name} {hand:?}: {:?}", stats.error);
+                // the dead ends are erased, what is left is the one solution
+                assert_eq!(&follower.path, bfs.get_path(), "{name} {hand:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn stops_when_circling_an_island() {
+        // no inner walls at all, the goal in the middle is never touched
+        let mut board = Board::new(0, 5, 5, CELL_SIZE);
+        for index in 0..board.cells.len() {
+            for neighbor in board.neighbors(index).into_iter().flatten() {
+                board.remove_wall(index, neighbor);
+            }
+        }
+        board.set_goal(board.get_index(2, 2));
+        for hand in [Hand::Left, Hand::Right] {
+            let stats = headless::solve(&board, &mut WallFollower::new(&board, hand), 1000);
+            assert!(!stats.solved);
+            assert_eq!(
+                stats.error.as_deref(),
+                Some("wall follower is walking in a loop")
+            );
+        }
+    }
+}
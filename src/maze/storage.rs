@@ -0,0 +1,486 @@
+use std::{fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::maze::{Board, CELL_SIZE, Walls, Wrap};
+
+/// Version of the json and binary format written by this module. Version 1
+/// files, written before boards could wrap, and version 2 files, written
+/// before boards could be masked, are still read.
+pub const VERSION: u32 = 3;
+
+const MAGIC: &[u8; 4] = b"MAZE";
+
+/// How a maze was made.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Metadata {
+    pub algorithm: String,
+    pub seed: Option<u64>,
+}
+
+/// A finished maze as it is written to disk.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedMaze {
+    pub version: u32,
+    pub width: usize,
+    pub height: usize,
+    pub entrance: usize,
+    pub exit: usize,
+    #[serde(default)]
+    pub wrap: Wrap,
+    pub metadata: Metadata,
+    /// the walls of every cell, in the order of `Board::cells`
+    pub walls: Vec<Walls>,
+    /// which cells are masked, in the same order, empty when none are
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub masked: Vec<bool>,
+}
+
+impl SavedMaze {
+    pub fn new(board: &Board, metadata: Metadata) -> Self {
+        Self {
+            version: VERSION,
+            width: board.width,
+            height: board.height,
+            entrance: board.start,
+            exit: board.goal,
+            wrap: board.wrap,
+            metadata,
+            walls: board.cells.iter().map(|cell| cell.walls.clone()).collect(),
+            masked: if board.cells.iter().any(|cell| cell.masked) {
+                board.cells.iter().map(|cell| cell.masked).collect()
+            } else {
+                Vec::new()
+            },
+        }
+    }
+
+    /**
+     * Build a board from the saved walls. The board uses the headless
+     * layout, set `x`, `y` and `cell_size` to show it in a window.
+     */
+    pub fn to_board(&self) -> Result<Board, String> {
+        if !supported(self.version) {
+            return Err(format!("unsupported version {}", self.version));
+        }
+        if cell_count(self.width, self.height)? != self.walls.len() {
+            return Err(format!(
+                "expected {}x{} cells, got {}",
+                self.width,
+                self.height,
+                self.walls.len()
+            ));
+        }
+        if self.entrance >= self.walls.len() || self.exit >= self.walls.len() {
+            return Err(String::from("entrance or exit is outside of the board"));
+        }
+        if !self.masked.is_empty() && self.masked.len() != self.walls.len() {
+            return Err(format!(
+                "expected {} masked flags, got {}",
+                self.walls.len(),
+                self.masked.len()
+            ));
+        }
+        let mut board = Board::with_wrap(0, self.width, self.height, CELL_SIZE, self.wrap);
+        for (cell, walls) in board.cells.iter_mut().zip(&self.walls) {
+            cell.walls = walls.clone();
+            cell.visited = true;
+        }
+        // masked cells are never carved, as on a freshly generated board
+        for (cell, &masked) in board.cells.iter_mut().zip(&self.masked) {
+            cell.masked = masked;
+            cell.visited = !masked;
+        }
+        if board.cells[self.entrance].masked || board.cells[self.exit].masked {
+            return Err(String::from("entrance or exit is on a masked cell"));
+        }
+        board.start = self.entrance;
+        board.goal = self.exit;
+        board.check_walls()?;
+        Ok(board)
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|err| err.to_string())
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let maze: SavedMaze = serde_json::from_str(json).map_err(|err| err.to_string())?;
+        if !supported(maze.version) {
+            return Err(format!("unsupported version {}", maze.version));
+        }
+        Ok(maze)
+    }
+
+    /**
+     * Compact binary form:
+     *
+     * "MAZE", version (u8), width, height, entrance, exit (u32), wrap
+     * (u8, 0 = none, 1 = cylinder, 2 = torus, missing in version 1),
+     * seed flag (u8) and seed (u64), algorithm length (u16) and utf-8 name,
+     * followed by the walls packed into bits: the top walls of the first
+     * row, the left walls of the first column and then the right and
+     * bottom wall of every cell. From version 3 on a flag (u8) follows,
+     * if it is set one bit per cell tells whether the cell is masked. All
+     * numbers are little endian.
+     */
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(MAGIC);
+        data.push(VERSION as u8);
+        for value in [self.width, self.height, self.entrance, self.exit] {
+            data.extend_from_slice(&(value as u32).to_le_bytes());
+        }
+        data.push(match self.wrap {
+            Wrap::None => 0,
+            Wrap::Cylinder => 1,
+            Wrap::Torus => 2,
+        });
+        data.push(self.metadata.seed.is_some() as u8);
+        data.extend_from_slice(&self.metadata.seed.unwrap_or_default().to_le_bytes());
+        let algorithm = self.metadata.algorithm.as_bytes();
+        data.extend_from_slice(&(algorithm.len() as u16).to_le_bytes());
+        data.extend_from_slice(algorithm);
+
+        let mut bits = BitWriter::default();
+        for x in 0..self.width {
+            bits.push(self.walls[x * self.height].top);
+        }
+        for y in 0..self.height {
+            bits.push(self.walls[y].left);
+        }
+        for walls in &self.walls {
+            bits.push(walls.right);
+            bits.push(walls.bottom);
+        }
+        data.extend_from_slice(&bits.bytes);
+
+        data.push(!self.masked.is_empty() as u8);
+        let mut bits = BitWriter::default();
+        for &masked in &self.masked {
+            bits.push(masked);
+        }
+        data.extend_from_slice(&bits.bytes);
+        data
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<Self, String> {
+        let mut reader = ByteReader { data, pos: 0 };
+        if reader.take(4)? != MAGIC {
+            return Err(String::from("not a maze file"));
+        }
+        let version = reader.take(1)?[0] as u32;
+        if !supported(version) {
+            return Err(format!("unsupported version {}", version));
+        }
+        let width = reader.u32()? as usize;
+        let height = reader.u32()? as usize;
+        let entrance = reader.u32()? as usize;
+        let exit = reader.u32()? as usize;
+        let wrap = if version >= 2 {
+            match reader.take(1)?[0] {
+                0 => Wrap::None,
+                1 => Wrap::Cylinder,
+                2 => Wrap::Torus,
+                value => return Err(format!("invalid wrap {}", value)),
+            }
+        } else {
+            Wrap::None
+        };
+        let has_seed = reader.take(1)?[0] != 0;
+        let seed = u64::from_le_bytes(reader.take(8)?.try_into().unwrap());
+        let length = u16::from_le_bytes(reader.take(2)?.try_into().unwrap()) as usize;
+        let algorithm = String::from_utf8(reader.take(length)?.to_vec())
+            .map_err(|_| String::from("algorithm name is not utf-8"))?;
+
+        let cells = cell_count(width, height)?;
+        // the outer top and left walls and two walls for every cell
+        let wall_bits = cells
+            .checked_mul(2)
+            .and_then(|bits| bits.checked_add(width))
+            .and_then(|bits| bits.checked_add(height))
+            .ok_or_else(|| too_large(width, height))?;
+        let bits = BitReader {
+            bytes: reader.take(wall_bits.div_ceil(8))?,
+        };
+
+        let mut walls = vec![Walls::default(); cells];
+        for x in 0..width {
+            walls[x * height].top = bits.get(x);
+        }
+        for (y, cell) in walls.iter_mut().enumerate().take(height) {
+            cell.left = bits.get(width + y);
+        }
+        let offset = width + height;
+        for index in 0..cells {
+            let right = bits.get(offset + 2 * index);
+            let bottom = bits.get(offset + 2 * index + 1);
+            walls[index].right = right;
+            walls[index].bottom = bottom;
+            // the neighbor to the right and below share these walls
+            if index + height < cells {
+                walls[index + height].left = right;
+            }
+            if !(index + 1).is_multiple_of(height) {
+                walls[index + 1].top = bottom;
+            }
+        }
+
+        let masked = if version >= 3 && reader.take(1)?[0] != 0 {
+            let bits = BitReader {
+                bytes: reader.take(cells.div_ceil(8))?,
+            };
+            (0..cells).map(|index| bits.get(index)).collect()
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            version,
+            width,
+            height,
+            entrance,
+            exit,
+            wrap,
+            metadata: Metadata {
+                algorithm,
+                seed: has_seed.then_some(seed),
+            },
+            walls,
+            masked,
+        })
+    }
+
+    /**
+     * Write the maze, as json when the file name ends with `.json` and in
+     * the binary form otherwise.
+     */
+    pub fn save<P: AsRef<Path>>(&self, file: P) -> Result<(), String> {
+        let file = file.as_ref();
+        let data = if is_json(file) {
+            self.to_json()?.into_bytes()
+        } else {
+            self.to_bytes()
+        };
+        fs::write(file, data).map_err(|err| format!("{}: {}", file.display(), err))
+    }
+
+    pub fn load<P: AsRef<Path>>(file: P) -> Result<Self, String> {
+        let file = file.as_ref();
+        let data = fs::read(file).map_err(|err| format!("{}: {}", file.display(), err))?;
+        if is_json(file) {
+            Self::from_json(&String::from_utf8(data).map_err(|err| err.to_string())?)
+        } else {
+            Self::from_bytes(&data)
+        }
+    }
+}
+
+/// Number of cells of a `width` x `height` board, an error for an empty or
+/// an impossibly large one.
+fn cell_count(width: usize, height: usize) -> Result<usize, String> {
+    if width == 0 || height == 0 {
+        return Err(format!("invalid board size {}x{}", width, height));
+    }
+    width
+        .checked_mul(height)
+        .ok_or_else(|| too_large(width, height))
+}
+
+fn too_large(width: usize, height: usize) -> String {
+    format!("board of {}x{} cells is too large", width, height)
+}
+
+fn supported(version: u32) -> bool {
+    (1..=VERSION).contains(&version)
+}
+
+fn is_json(file: &Path) -> bool {
+    file.extension().is_some_and(|ext| ext == "json")
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl BitWriter {
+    fn push(&mut self, bit: bool) {
+        if self.len.is_multiple_of(8) {
+            self.bytes.push(0);
+        }
+        if bit {
+            *self.bytes.last_mut().unwrap() |= 1 << (self.len % 8);
+        }
+        self.len += 1;
+    }
+}
+
+struct BitReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl BitReader<'_> {
+    fn get(&self, index: usize) -> bool {
+        self.bytes[index / 8] & (1 << (index % 8)) != 0
+    }
+}
+
+struct ByteReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        if self.data.len() - self.pos < len {
+            return Err(String::from("unexpected end of file"));
+        }
+        let res = &self.data[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(res)
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::{generator, headless, mask::Mask};
+
+    fn generate(mut board: Board) -> Board {
+        let mut generator = generator::by_name("recursive backtracker", &mut board, 5).unwrap();
+        headless::run(&mut board, generator.as_mut());
+        board
+    }
+
+    fn boards() -> Vec<Board> {
+        let mut boards: Vec<Board> = [Wrap::None, Wrap::Cylinder, Wrap::Torus]
+            .into_iter()
+            .map(|wrap| generate(Board::with_wrap(0, 9, 6, CELL_SIZE, wrap)))
+            .collect();
+        let mut mask = Mask::new(9, 6);
+        mask.set(4, 2, false);
+        mask.set(4, 3, false);
+        mask.set(0, 5, false);
+        boards.push(generate(Board::with_mask(0, &mask, CELL_SIZE).unwrap()));
+        boards
+    }
+
+    fn saved(board: &Board) -> SavedMaze {
+        SavedMaze::new(
+            board,
+            Metadata {
+                algorithm: String::from("recursive backtracker"),
+                seed: Some(5),
+            },
+        )
+    }
+
+    #[test]
+    fn json_round_trip() {
+        for board in boards() {
+            let maze = saved(&board);
+            let loaded = SavedMaze::from_json(&maze.to_json().unwrap()).unwrap();
+            assert_eq!(loaded, maze);
+            assert_eq!(loaded.to_board().unwrap(), board);
+        }
+    }
+
+    #[test]
+    fn binary_round_trip() {
+        for board in boards() {
+            let maze = saved(&board);
+            let loaded = SavedMaze::from_bytes(&maze.to_bytes()).unwrap();
+            assert_eq!(loaded, maze);
+            assert_eq!(loaded.to_board().unwrap(), board);
+        }
+    }
+
+    #[test]
+    fn reads_version_2() {
+        let board = &boards()[1];
+        let mut data = saved(board).to_bytes();
+        data[4] = 2;
+        // version 2 ends after the walls, without the mask flag
+        data.pop();
+        let loaded = SavedMaze::from_bytes(&data).unwrap();
+        assert_eq!(loaded.version, 2);
+        assert_eq!(&loaded.to_board().unwrap(), board);
+    }
+
+    #[test]
+    fn large_board() {
+        let mut board = Board::new(0, 1000, 1000, CELL_SIZE);
+        let mut generator = generator::by_name("binary tree", &mut board, 5).unwrap();
+        headless::run(&mut board, generator.as_mut());
+        let data = saved(&board).to_bytes();
+        // two bits per cell
+        assert!(data.len() < 2 * 1000 * 1000 / 8 + 1000);
+        let loaded = SavedMaze::from_bytes(&data).unwrap().to_board().unwrap();
+        assert_eq!(loaded, board);
+    }
+
+    #[test]
+    fn bad_header() {
+        let data = saved(&boards()[0]).to_bytes();
+        for end in 0..30 {
+            assert!(SavedMaze::from_bytes(&data[..end]).is_err(), "{end}");
+        }
+        // width and height are bytes 5 to 12
+        for (width, height) in [(u32::MAX, u32::MAX), (u32::MAX, 1), (0, 6)] {
+            let mut data = data.clone();
+            data[5..9].copy_from_slice(&width.to_le_bytes());
+            data[9..13].copy_from_slice(&height.to_le_bytes());
+            assert!(SavedMaze::from_bytes(&data).is_err(), "{width}x{height}");
+        }
+
+        let mut maze = saved(&boards()[0]);
+        maze.width = usize::MAX;
+        maze.height = usize::MAX;
+        let json = maze.to_json().unwrap();
+        assert!(SavedMaze::from_json(&json).unwrap().to_board().is_err());
+    }
+
+    #[test]
+    fn masked_entrance() {
+        let board = &boards()[3];
+        let mut maze = saved(board);
+        maze.entrance = board.get_index(4, 2);
+        assert!(maze.to_board().is_err());
+    }
+
+    #[test]
+    fn malformed() {
+        let board = &boards()[0];
+        let data = saved(board).to_bytes();
+        assert!(SavedMaze::from_bytes(b"").is_err());
+        assert!(SavedMaze::from_bytes(b"ZAME").is_err());
+        assert!(SavedMaze::from_bytes(&data[..data.len() - 2]).is_err());
+        let mut future = data.clone();
+        future[4] = VERSION as u8 + 1;
+        assert!(SavedMaze::from_bytes(&future).is_err());
+        let mut wrap = data;
+        wrap[21] = 3;
+        assert!(SavedMaze::from_bytes(&wrap).is_err());
+
+        assert!(SavedMaze::from_json("").is_err());
+        assert!(SavedMaze::from_json("{\"version\": 1}").is_err());
+        let mut maze = saved(board);
+        maze.version = VERSION + 1;
+        assert!(SavedMaze::from_json(&maze.to_json().unwrap()).is_err());
+        let mut maze = saved(board);
+        maze.walls.pop();
+        assert!(maze.to_board().is_err());
+        let mut maze = saved(board);
+        maze.exit = maze.walls.len();
+        assert!(maze.to_board().is_err());
+        let mut maze = saved(board);
+        maze.walls[0].right = !maze.walls[0].right;
+        assert!(maze.to_board().is_err());
+    }
+}
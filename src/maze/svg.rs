@@ -0,0 +1,261 @@
+use std::{f32::consts::PI, fmt::Write as _, fs, io, path::Path};
+
+use crate::{
+    maze::{
+        render::{Renderer, Style},
+        topology::Topology,
+    },
+    raylib::{Color, Vector2},
+};
+
+#[derive(Clone, Debug)]
+pub struct SvgOptions {
+    pub style: Style,
+    /// size of one cell in the svg document
+    pub cell_size: f32,
+    /// empty space around the board
+    pub margin: f32,
+    pub background: Option<Color>,
+    /// shade the cells the generator has not visited yet
+    pub unvisited: bool,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            style: Style {
+                wall_width: 2.0,
+                path_width: 2.0,
+                ..Style::default()
+            },
+            cell_size: 20.0,
+            margin: 10.0,
+            background: None,
+            unvisited: true,
+        }
+    }
+}
+
+/// Renderer that collects svg elements. Coordinates are given in board
+/// space (as used for the raylib window) and mapped to the document using
+/// the cell size and margin from the options. Line widths are not scaled.
+pub struct SvgRenderer {
+    origin: Vector2,
+    scale: f32,
+    margin: f32,
+    width: f32,
+    height: f32,
+    body: String,
+}
+
+impl SvgRenderer {
+    pub fn new<T: Topology + ?Sized>(board: &T, options: &SvgOptions) -> Self {
+        let bounds = board.bounds();
+        let scale = options.cell_size / board.cell_size();
+        let mut renderer = Self {
+            origin: Vector2::new(bounds.x, bounds.y),
+            scale,
+            margin: options.margin,
+            width: bounds.width * scale + 2.0 * options.margin,
+            height: bounds.height * scale + 2.0 * options.margin,
+            body: String::new(),
+        };
+        if let Some(background) = options.background {
+            let _ = writeln!(
+                renderer.body,
+                r#"<rect x="0" y="0" width="{}" height="{}" {}/>"#,
+                renderer.width,
+                renderer.height,
+                fill(background)
+            );
+        }
+        renderer
+    }
+
+    fn point(&self, point: Vector2) -> Vector2 {
+        Vector2::new(
+            (point.x - self.origin.x) * self.scale + self.margin,
+            (point.y - self.origin.y) * self.scale + self.margin,
+        )
+    }
+
+    /// Close the document and return it.
+    pub fn finish(self) -> String {
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n{}</svg>\n",
+            self.body,
+            w = self.width,
+            h = self.height
+        )
+    }
+}
+
+fn rgb(color: Color) -> String {
+    format!("rgb({},{},{})", color.r, color.g, color.b)
+}
+
+fn fill(color: Color) -> String {
+    if color.a == 255 {
+        format!(r#"fill="{}""#, rgb(color))
+    } else {
+        format!(
+            r#"fill="{}" fill-opacity="{:.3}""#,
+            rgb(color),
+            color.a as f32 / 255.0
+        )
+    }
+}
+
+fn stroke(color: Color, width: f32) -> String {
+    let mut res = format!(
+        r#"stroke="{}" stroke-width="{}" stroke-linecap="square""#,
+        rgb(color),
+        width
+    );
+    if color.a != 255 {
+        let _ = write!(res, r#" stroke-opacity="{:.3}""#, color.a as f32 / 255.0);
+    }
+    res
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+impl Renderer for SvgRenderer {
+    fn line(&mut self, start: Vector2, end: Vector2, thick: f32, color: Color) {
+        let (start, end) = (self.point(start), self.point(end));
+        let _ = writeln!(
+            self.body,
+            r#"<line x1="{}" y1="{}" x2="{}" y2="{}" {}/>"#,
+            start.x,
+            start.y,
+            end.x,
+            end.y,
+            stroke(color, thick)
+        );
+    }
+
+    fn rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: Color) {
+        let pos = self.point(Vector2::new(x, y));
+        let _ = writeln!(
+            self.body,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" {}/>"#,
+            pos.x,
+            pos.y,
+            width * self.scale,
+            height * self.scale,
+            fill(color)
+        );
+    }
+
+    fn circle(&mut self, center: Vector2, radius: f32, color: Color) {
+        let center = self.point(center);
+        let _ = writeln!(
+            self.body,
+            r#"<circle cx="{}" cy="{}" r="{}" {}/>"#,
+            center.x,
+            center.y,
+            radius * self.scale,
+            fill(color)
+        );
+    }
+
+    fn triangle(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color) {
+        let (v1, v2, v3) = (self.point(v1), self.point(v2), self.point(v3));
+        let _ = writeln!(
+            self.body,
+            r#"<polygon points="{},{} {},{} {},{}" {}/>"#,
+            v1.x,
+            v1.y,
+            v2.x,
+            v2.y,
+            v3.x,
+            v3.y,
+            fill(color)
+        );
+    }
+
+    fn text(&mut self, text: &str, x: f32, y: f32, size: f32, color: Color) {
+        let pos = self.point(Vector2::new(x, y));
+        let _ = writeln!(
+            self.body,
+            r#"<text x="{}" y="{}" font-size="{}" dominant-baseline="hanging" {}>{}</text>"#,
+            pos.x,
+            pos.y,
+            size,
+            fill(color),
+            escape(text)
+        );
+    }
+
+    fn arc(
+        &mut self,
+        center: Vector2,
+        radius: f32,
+        start: f32,
+        end: f32,
+        thick: f32,
+        color: Color,
+    ) {
+        // a single arc command can not describe a full circle
+        if (end - start).abs() > PI {
+            let middle = (start + end) / 2.0;
+            self.arc(center, radius, start, middle, thick, color);
+            self.arc(center, radius, middle, end, thick, color);
+            return;
+        }
+        let center = self.point(center);
+        let radius = radius * self.scale;
+        let (x0, y0) = (
+            center.x + radius * start.cos(),
+            center.y - radius * start.sin(),
+        );
+        let (x1, y1) = (center.x + radius * end.cos(), center.y - radius * end.sin());
+        // the y axis points down, so counter clockwise is the negative sweep
+        let sweep = if end > start { 0 } else { 1 };
+        let _ = writeln!(
+            self.body,
+            r#"<path d="M {} {} A {} {} 0 0 {} {} {}" fill="none" {}/>"#,
+            x0,
+            y0,
+            radius,
+            radius,
+            sweep,
+            x1,
+            y1,
+            stroke(color, thick)
+        );
+    }
+}
+
+/**
+ * Render the board and an optional solution path as a svg document.
+ */
+pub fn to_svg<T: Topology + ?Sized>(
+    board: &T,
+    solution: Option<&[usize]>,
+    options: &SvgOptions,
+) -> String {
+    let mut renderer = SvgRenderer::new(board, options);
+    let mut style = options.style.clone();
+    if !options.unvisited {
+        style.unvisited_color.a = 0;
+    }
+    board.draw_board(&style, &mut renderer);
+    if let Some(solution) = solution {
+        board.draw_path(solution, &style, &mut renderer);
+    }
+    renderer.finish()
+}
+
+pub fn write_svg<P: AsRef<Path>, T: Topology + ?Sized>(
+    file: P,
+    board: &T,
+    solution: Option<&[usize]>,
+    options: &SvgOptions,
+) -> io::Result<()> {
+    fs::write(file, to_svg(board, solution, options))
+}
@@ -0,0 +1,128 @@
+use crate::{
+    maze::{
+        Board, path,
+        render::{Renderer, Style},
+    },
+    raylib::{Rectangle, Vector2},
+};
+
+/// The shape of a grid: which cells touch, where the walls between them
+/// are and how it is drawn. Generators and solvers that only need these
+/// operations work on every grid, the others use `Board` directly.
+pub trait Topology {
+    fn cell_count(&self) -> usize;
+    /// the cells sharing a side with `cell`
+    fn adjacent(&self, cell: usize) -> Vec<usize>;
+    /// the adjacent cells that can be reached without crossing a wall
+    fn passages(&self, cell: usize) -> Vec<usize>;
+    fn remove_wall(&mut self, cell: usize, neighbor: usize);
+    fn visited(&self, cell: usize) -> bool;
+    fn set_visited(&mut self, cell: usize);
+    fn start(&self) -> usize;
+    fn goal(&self) -> usize;
+
+    /// whether the cell is part of the maze, masked cells are not
+    fn enabled(&self, _cell: usize) -> bool {
+        true
+    }
+
+    /// the cells that are part of the maze
+    fn enabled_cells(&self) -> Vec<usize> {
+        (0..self.cell_count())
+            .filter(|&cell| self.enabled(cell))
+            .collect()
+    }
+
+    /// center of the cell on screen
+    fn center(&self, cell: usize) -> Vector2;
+    /// typical size of a cell on screen, used to scale markers
+    fn cell_size(&self) -> f32;
+    /// the area the grid covers on screen
+    fn bounds(&self) -> Rectangle;
+    fn draw_board(&self, style: &Style, renderer: &mut dyn Renderer);
+
+    /// Draw a path through the grid, by default as lines between the cell
+    /// centers.
+    fn draw_path(&self, path: &[usize], style: &Style, renderer: &mut dyn Renderer) {
+        for pair in path.windows(2) {
+            renderer.line(
+                self.center(pair[0]),
+                self.center(pair[1]),
+                style.path_width,
+                style.path_color,
+            );
+        }
+        if let (Some(&first), Some(&last)) = (path.first(), path.last()) {
+            for cell in [first, last] {
+                renderer.circle(self.center(cell), self.cell_size() / 10.0, style.path_color);
+            }
+        }
+    }
+}
+
+impl Topology for Board {
+    fn cell_count(&self) -> usize {
+        self.cells.len()
+    }
+
+    fn adjacent(&self, cell: usize) -> Vec<usize> {
+        self.neighbors(cell).into_iter().flatten().collect()
+    }
+
+    fn passages(&self, cell: usize) -> Vec<usize> {
+        Board::passages(self, cell)
+    }
+
+    fn remove_wall(&mut self, cell: usize, neighbor: usize) {
+        Board::remove_wall(self, cell, neighbor)
+    }
+
+    fn visited(&self, cell: usize) -> bool {
+        self.cells[cell].visited
+    }
+
+    fn set_visited(&mut self, cell: usize) {
+        self.cells[cell].visited = true;
+    }
+
+    fn start(&self) -> usize {
+        self.start
+    }
+
+    fn goal(&self) -> usize {
+        self.goal
+    }
+
+    fn enabled(&self, cell: usize) -> bool {
+        !self.cells[cell].masked
+    }
+
+    fn center(&self, cell: usize) -> Vector2 {
+        let cell = &self.cells[cell];
+        Vector2::new(
+            (self.x + cell.x * self.cell_size + self.cell_size / 2) as f32,
+            (self.y + cell.y * self.cell_size + self.cell_size / 2) as f32,
+        )
+    }
+
+    fn cell_size(&self) -> f32 {
+        self.cell_size as f32
+    }
+
+    fn bounds(&self) -> Rectangle {
+        Rectangle::new(
+            self.x as f32,
+            self.y as f32,
+            (self.width * self.cell_size) as f32,
+            (self.height * self.cell_size) as f32,
+        )
+    }
+
+    fn draw_board(&self, style: &Style, renderer: &mut dyn Renderer) {
+        self.draw_styled(renderer, style);
+    }
+
+    fn draw_path(&self, path: &[usize], style: &Style, renderer: &mut dyn Renderer) {
+        path::draw_path_styled(self, path, style, renderer);
+    }
+}
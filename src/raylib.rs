@@ -241,15 +241,30 @@ pub enum KeyboardKey {
     KeyVolumeDown = 25, // Key: Android volume down button
 }
 
+pub enum MouseButton {
+    Left = 0,
+    Right = 1,
+    Middle = 2,
+}
+
+#[cfg(feature = "raylib")]
+#[allow(non_snake_case)]
+pub fn IsMouseButtonPressed(button: MouseButton) -> bool {
+    unsafe { RayIsMouseButtonPressed(button as i32) }
+}
+#[cfg(feature = "raylib")]
 #[allow(non_snake_case)]
 pub fn IsKeyPressed(key: KeyboardKey) -> bool {
     unsafe { RayIsKeyPressed(key as i32) }
 }
+#[cfg(feature = "raylib")]
 #[allow(non_snake_case)]
 pub fn IsKeyDown(key: KeyboardKey) -> bool {
     unsafe { RayIsKeyDown(key as i32) }
 }
 
+// the types above are plain data, only the functions need the library
+#[cfg(feature = "raylib")]
 unsafe extern "C" {
     pub fn BeginDrawing();
     pub fn EndDrawing();
@@ -283,6 +298,9 @@ unsafe extern "C" {
     pub fn RayIsKeyPressed(key: i32) -> bool;
     #[link_name = "IsKeyDown"]
     pub fn RayIsKeyDown(key: i32) -> bool;
+    #[link_name = "IsMouseButtonPressed"]
+    pub fn RayIsMouseButtonPressed(button: i32) -> bool;
+    pub fn GetMousePosition() -> Vector2;
 
     pub fn Vector2Subtract(v1: Vector2, v2: Vector2) -> Vector2;
     pub fn Vector2Add(v1: Vector2, v2: Vector2) -> Vector2;